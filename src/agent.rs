@@ -0,0 +1,426 @@
+//! Multi-step tool-calling agent loop
+//!
+//! Drives an LLM through a reasoning loop over the tools exposed by a
+//! connected [`MCPClient`]: collect every server's tools, hand them to the
+//! model as callable functions, dispatch whatever tool calls it requests
+//! via [`MCPClient::call_tool`], feed the results back as tool-result
+//! turns, and repeat until the model returns a final answer or `max_steps`
+//! is reached. Mirrors how [`crate::completion::CompletionProvider`] keeps
+//! the engine itself agnostic of where completions come from: the LLM
+//! backend here is a pluggable [`AgentLlm`], not a hard-wired API client.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::client::MCPClient;
+
+/// A tool exposed to the LLM, scoped to the server that provides it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentTool {
+    pub server: String,
+    pub tool: String,
+    pub description: Option<String>,
+    pub input_schema: Option<Value>,
+}
+
+/// One request from the LLM to invoke a tool.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallRequest {
+    pub server: String,
+    pub tool: String,
+    pub arguments: Option<Value>,
+}
+
+/// A turn in the conversation, fed back to the LLM on the next step.
+#[derive(Debug, Clone)]
+pub enum AgentMessage {
+    /// The original task given to the agent.
+    User(String),
+    /// A final or intermediate message from the model.
+    Assistant(String),
+    /// The outcome of dispatching a `ToolCallRequest`.
+    ToolResult {
+        server: String,
+        tool: String,
+        content: String,
+        is_error: bool,
+    },
+}
+
+/// What the LLM decided to do given the conversation so far.
+#[derive(Debug, Clone)]
+pub enum AgentStep {
+    /// Call one or more tools before continuing.
+    ToolCalls(Vec<ToolCallRequest>),
+    /// The task is done; this is the answer to surface to the caller.
+    FinalAnswer(String),
+}
+
+/// Pluggable LLM backend driving the agent loop. Implementations turn the
+/// conversation-so-far plus the available tools into the model's next
+/// step.
+#[async_trait::async_trait]
+pub trait AgentLlm: Send + Sync {
+    async fn next_step(&self, messages: &[AgentMessage], tools: &[AgentTool]) -> Result<AgentStep>;
+}
+
+/// Drives an [`AgentLlm`] through a tool-calling loop against a connected
+/// [`MCPClient`], guarding against infinite loops and unknown tools.
+pub struct AgentLoop<'a> {
+    client: &'a MCPClient,
+    llm: &'a dyn AgentLlm,
+    max_steps: usize,
+}
+
+impl<'a> AgentLoop<'a> {
+    /// Create a loop that will take at most `max_steps` reasoning steps
+    /// before giving up.
+    pub fn new(client: &'a MCPClient, llm: &'a dyn AgentLlm, max_steps: usize) -> Self {
+        Self {
+            client,
+            llm,
+            max_steps,
+        }
+    }
+
+    /// Run the loop for `prompt`, returning the model's final answer.
+    pub async fn run(&self, prompt: &str) -> Result<String> {
+        let tools = self.collect_tools().await?;
+        let mut messages = vec![AgentMessage::User(prompt.to_string())];
+        let mut last_calls: Option<Vec<ToolCallRequest>> = None;
+
+        for step in 0..self.max_steps {
+            match self.llm.next_step(&messages, &tools).await? {
+                AgentStep::FinalAnswer(answer) => {
+                    messages.push(AgentMessage::Assistant(answer.clone()));
+                    return Ok(answer);
+                }
+                AgentStep::ToolCalls(calls) => {
+                    if calls.is_empty() {
+                        return Err(anyhow!(
+                            "step {}: model returned neither tool calls nor a final answer",
+                            step
+                        ));
+                    }
+
+                    if last_calls.as_deref() == Some(calls.as_slice()) {
+                        return Err(anyhow!(
+                            "step {}: model repeated the same tool call(s) it just made; aborting to avoid a loop",
+                            step
+                        ));
+                    }
+
+                    for call in &calls {
+                        if !tools
+                            .iter()
+                            .any(|t| t.server == call.server && t.tool == call.tool)
+                        {
+                            return Err(anyhow!(
+                                "server '{}' has no tool named '{}'",
+                                call.server,
+                                call.tool
+                            ));
+                        }
+
+                        let result = self
+                            .client
+                            .call_tool(&call.server, &call.tool, call.arguments.clone())
+                            .await?;
+
+                        let content = result
+                            .content
+                            .iter()
+                            .filter_map(|c| c.text.clone())
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        messages.push(AgentMessage::ToolResult {
+                            server: call.server.clone(),
+                            tool: call.tool.clone(),
+                            content,
+                            is_error: result.is_error,
+                        });
+                    }
+
+                    last_calls = Some(calls);
+                }
+            }
+        }
+
+        Err(anyhow!(
+            "agent loop exceeded max_steps ({}) without a final answer",
+            self.max_steps
+        ))
+    }
+
+    async fn collect_tools(&self) -> Result<Vec<AgentTool>> {
+        let all = self.client.list_all_tools().await?;
+
+        Ok(all
+            .into_iter()
+            .flat_map(|(server, tools)| {
+                tools.into_iter().map(move |t| AgentTool {
+                    server: server.clone(),
+                    tool: t.name,
+                    description: t.description,
+                    input_schema: t.input_schema,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Composite function name passed to the chat-completions API, since a
+/// single server can expose a tool with the same name as another server's.
+fn function_name(server: &str, tool: &str) -> String {
+    format!("{}__{}", server, tool)
+}
+
+/// Inverse of [`function_name`]: splits on the first `__` separator.
+fn split_function_name(name: &str) -> Result<(String, String)> {
+    name.split_once("__")
+        .map(|(server, tool)| (server.to_string(), tool.to_string()))
+        .ok_or_else(|| anyhow!("malformed tool call name from model: '{}'", name))
+}
+
+/// [`AgentLlm`] backed by an OpenAI-compatible `/chat/completions` endpoint
+/// (OpenAI itself, or any server implementing the same function-calling
+/// wire format, e.g. a local Ollama/vLLM front end). Configured entirely
+/// from the environment so it can be dropped into the CLI without extra
+/// plumbing:
+///
+/// - `OPENAI_API_KEY` (required)
+/// - `OPENAI_API_BASE` (default `https://api.openai.com/v1`)
+/// - `OPENAI_MODEL` (default `gpt-4o-mini`)
+pub struct OpenAiAgentLlm {
+    http: reqwest::Client,
+    api_base: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiAgentLlm {
+    /// Build a client from `OPENAI_API_KEY`/`OPENAI_API_BASE`/`OPENAI_MODEL`.
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .map_err(|_| anyhow!("OPENAI_API_KEY is not set; required to run the agent"))?;
+        let api_base = std::env::var("OPENAI_API_BASE")
+            .unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
+        let model = std::env::var("OPENAI_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            api_base,
+            api_key,
+            model,
+        })
+    }
+
+    fn render_messages(&self, messages: &[AgentMessage]) -> Vec<serde_json::Value> {
+        messages
+            .iter()
+            .map(|message| match message {
+                AgentMessage::User(text) => serde_json::json!({"role": "user", "content": text}),
+                AgentMessage::Assistant(text) => {
+                    serde_json::json!({"role": "assistant", "content": text})
+                }
+                AgentMessage::ToolResult {
+                    server,
+                    tool,
+                    content,
+                    is_error,
+                } => serde_json::json!({
+                    "role": "user",
+                    "content": format!(
+                        "Result of calling '{}' on server '{}' ({}):\n{}",
+                        tool,
+                        server,
+                        if *is_error { "error" } else { "ok" },
+                        content
+                    ),
+                }),
+            })
+            .collect()
+    }
+
+    fn render_tools(&self, tools: &[AgentTool]) -> Vec<serde_json::Value> {
+        tools
+            .iter()
+            .map(|tool| {
+                serde_json::json!({
+                    "type": "function",
+                    "function": {
+                        "name": function_name(&tool.server, &tool.tool),
+                        "description": tool.description.clone().unwrap_or_default(),
+                        "parameters": tool.input_schema.clone().unwrap_or_else(|| serde_json::json!({"type": "object", "properties": {}})),
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AgentLlm for OpenAiAgentLlm {
+    async fn next_step(&self, messages: &[AgentMessage], tools: &[AgentTool]) -> Result<AgentStep> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "messages": self.render_messages(messages),
+            "tools": self.render_tools(tools),
+        });
+
+        let response: serde_json::Value = self
+            .http
+            .post(format!("{}/chat/completions", self.api_base))
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let message = response
+            .get("choices")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("message"))
+            .ok_or_else(|| anyhow!("malformed chat completion response: missing choices[0].message"))?;
+
+        if let Some(tool_calls) = message.get("tool_calls").and_then(|v| v.as_array()) {
+            let calls = tool_calls
+                .iter()
+                .map(|call| {
+                    let name = call["function"]["name"]
+                        .as_str()
+                        .ok_or_else(|| anyhow!("tool call missing function name"))?;
+                    let (server, tool) = split_function_name(name)?;
+                    let arguments = call["function"]["arguments"]
+                        .as_str()
+                        .map(serde_json::from_str)
+                        .transpose()?;
+
+                    Ok(ToolCallRequest {
+                        server,
+                        tool,
+                        arguments,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            return Ok(AgentStep::ToolCalls(calls));
+        }
+
+        let content = message
+            .get("content")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(AgentStep::FinalAnswer(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MCPConfig;
+    use std::sync::Mutex;
+
+    /// Scripted `AgentLlm` that returns a fixed sequence of steps in order.
+    struct ScriptedLlm {
+        steps: Mutex<std::vec::IntoIter<AgentStep>>,
+    }
+
+    impl ScriptedLlm {
+        fn new(steps: Vec<AgentStep>) -> Self {
+            Self {
+                steps: Mutex::new(steps.into_iter()),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AgentLlm for ScriptedLlm {
+        async fn next_step(
+            &self,
+            _messages: &[AgentMessage],
+            _tools: &[AgentTool],
+        ) -> Result<AgentStep> {
+            self.steps
+                .lock()
+                .unwrap()
+                .next()
+                .ok_or_else(|| anyhow!("scripted LLM ran out of steps"))
+        }
+    }
+
+    fn empty_config() -> MCPConfig {
+        MCPConfig {
+            mcp_servers: indexmap::IndexMap::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tool_call_request_equality_for_dedup() {
+        let a = ToolCallRequest {
+            server: "fs".to_string(),
+            tool: "read_file".to_string(),
+            arguments: Some(serde_json::json!({"path": "/tmp/x"})),
+        };
+        let b = a.clone();
+        assert_eq!(a, b);
+    }
+
+    #[tokio::test]
+    async fn test_immediate_final_answer_short_circuits() {
+        let client = MCPClient::new(empty_config());
+        let llm = ScriptedLlm::new(vec![AgentStep::FinalAnswer("42".to_string())]);
+        let agent = AgentLoop::new(&client, &llm, 5);
+
+        let answer = agent.run("what is the answer?").await.unwrap();
+        assert_eq!(answer, "42");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tool_surfaces_clear_error() {
+        let client = MCPClient::new(empty_config());
+        let llm = ScriptedLlm::new(vec![AgentStep::ToolCalls(vec![ToolCallRequest {
+            server: "filesystem".to_string(),
+            tool: "read_file".to_string(),
+            arguments: None,
+        }])]);
+        let agent = AgentLoop::new(&client, &llm, 5);
+
+        let err = agent.run("read a file").await.unwrap_err();
+        assert!(err.to_string().contains("no tool named 'read_file'"));
+    }
+
+    #[test]
+    fn test_final_answer_step_is_distinct_from_tool_calls() {
+        let final_step = AgentStep::FinalAnswer("done".to_string());
+        let tool_step = AgentStep::ToolCalls(vec![ToolCallRequest {
+            server: "fs".to_string(),
+            tool: "read_file".to_string(),
+            arguments: None,
+        }]);
+
+        assert!(matches!(final_step, AgentStep::FinalAnswer(_)));
+        assert!(matches!(tool_step, AgentStep::ToolCalls(_)));
+    }
+
+    #[test]
+    fn test_function_name_roundtrip() {
+        let name = function_name("filesystem", "read_file");
+        assert_eq!(name, "filesystem__read_file");
+        assert_eq!(
+            split_function_name(&name).unwrap(),
+            ("filesystem".to_string(), "read_file".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_function_name_rejects_malformed_input() {
+        assert!(split_function_name("read_file").is_err());
+    }
+}
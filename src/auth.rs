@@ -0,0 +1,93 @@
+//! Pluggable authentication for remote MCP connections.
+//!
+//! Each [`crate::config::AuthMethod`] maps to a small `Authenticator` that
+//! knows how to turn itself into HTTP headers, injected on every request
+//! and on the SSE subscription by [`crate::client::MCPClient`].
+
+use std::collections::HashMap;
+
+use crate::config::{AuthMethod, Env};
+
+/// Supplies headers to inject into outgoing requests for a remote MCP
+/// connection.
+pub trait Authenticator: Send + Sync {
+    fn headers(&self) -> HashMap<String, String>;
+}
+
+/// Approves anything without adding headers, for local/trusted servers.
+pub struct NoAuth;
+
+impl Authenticator for NoAuth {
+    fn headers(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+}
+
+struct BearerAuth {
+    token: String,
+}
+
+impl Authenticator for BearerAuth {
+    fn headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), format!("Bearer {}", self.token));
+        headers
+    }
+}
+
+struct StaticSecretAuth {
+    key: String,
+}
+
+impl Authenticator for StaticSecretAuth {
+    fn headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert("X-Api-Key".to_string(), self.key.clone());
+        headers
+    }
+}
+
+struct HeaderAuth {
+    name: String,
+    value: String,
+}
+
+impl Authenticator for HeaderAuth {
+    fn headers(&self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert(self.name.clone(), self.value.clone());
+        headers
+    }
+}
+
+/// Build the `Authenticator` for a configured `AuthMethod`, expanding any
+/// `${env:VAR}` references in token/secret values first so credentials
+/// aren't hard-coded in config files.
+pub fn authenticator_for(method: &AuthMethod, env: &dyn Env) -> Box<dyn Authenticator> {
+    match method {
+        AuthMethod::None => Box::new(NoAuth),
+        AuthMethod::Bearer { token } => Box::new(BearerAuth {
+            token: expand_env(token, env),
+        }),
+        AuthMethod::StaticSecret { key } => Box::new(StaticSecretAuth {
+            key: expand_env(key, env),
+        }),
+        AuthMethod::Header { name, value } => Box::new(HeaderAuth {
+            name: name.clone(),
+            value: expand_env(value, env),
+        }),
+    }
+}
+
+/// Expand `${env:VAR}` references in a single config value, mirroring
+/// `ConfigLoader::expand_variables`'s handling of the same syntax.
+fn expand_env(value: &str, env: &dyn Env) -> String {
+    let env_regex = regex::Regex::new(r"\$\{env:([^}]+)\}").unwrap();
+    let mut result = value.to_string();
+    for cap in env_regex.captures_iter(value) {
+        if let Some(replacement) = env.get(&cap[1]) {
+            result = result.replace(&cap[0], &replacement);
+        }
+    }
+    result
+}
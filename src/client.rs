@@ -7,8 +7,14 @@ use rmcp::{
 };
 use serde_json::Value;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 use crate::config::{MCPConfig, ServerConfig};
@@ -19,6 +25,9 @@ pub struct MCPServerConnection {
     pub config: ServerConfig,
     pub service: Option<Box<dyn MCPService>>,
     pub status: ConnectionStatus,
+    /// Guards against the supervisor overlapping a reconnect attempt for
+    /// this server with one already in flight.
+    reconnecting: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,9 +35,53 @@ pub enum ConnectionStatus {
     Disconnected,
     Connecting,
     Connected,
+    /// A background reconnect attempt is in flight, on the given attempt
+    /// number (1-based) of the supervisor's exponential backoff schedule.
+    Reconnecting { attempt: u32 },
     Failed(String),
 }
 
+/// Tuning knobs for the background connection supervisor that [`MCPClient::initialize`]
+/// spawns to keep connections alive.
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// How often each `Connected` server is probed with a lightweight
+    /// `list_tools` round-trip.
+    pub ping_interval: Duration,
+    /// Delay after the first failed reconnect attempt before the next one;
+    /// the first attempt runs immediately.
+    pub base_delay: Duration,
+    /// Upper bound the exponential backoff delay is capped at.
+    pub max_delay: Duration,
+    /// Number of reconnect attempts before giving up and leaving the
+    /// server `Failed` until the next probe tick retries it.
+    pub max_attempts: u32,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            max_attempts: 5,
+        }
+    }
+}
+
+/// How `call_tool_routed` behaves when more than one connected server
+/// exposes a tool with the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolConflictPolicy {
+    /// Refuse to route and return an error naming the conflicting servers.
+    Error,
+    /// Always route to whichever server was indexed first.
+    FirstWins,
+    /// Balance calls across the servers, failing over to the next healthy
+    /// one if a call fails.
+    RoundRobin,
+}
+
 /// Trait for abstracting MCP service operations
 #[async_trait::async_trait]
 pub trait MCPService: Send + Sync {
@@ -81,16 +134,87 @@ pub struct ResourceContent {
 
 /// Main MCP client that manages multiple server connections
 pub struct MCPClient {
-    connections: IndexMap<String, MCPServerConnection>,
+    connections: Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
     config: MCPConfig,
+    supervisor: SupervisorConfig,
+    supervisor_handle: Option<tokio::task::JoinHandle<()>>,
+    supervisor_shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    /// Tool name -> servers exposing it, in the order connections were
+    /// initialized. Rebuilt on (re)connect.
+    tool_index: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    tool_cursors: Arc<RwLock<HashMap<String, Arc<AtomicUsize>>>>,
+    conflict_policy: ToolConflictPolicy,
 }
 
 impl MCPClient {
     /// Create a new MCP client with the given configuration
     pub fn new(config: MCPConfig) -> Self {
         Self {
-            connections: IndexMap::new(),
+            connections: Arc::new(RwLock::new(IndexMap::new())),
             config,
+            supervisor: SupervisorConfig::default(),
+            supervisor_handle: None,
+            supervisor_shutdown: None,
+            tool_index: Arc::new(RwLock::new(HashMap::new())),
+            tool_cursors: Arc::new(RwLock::new(HashMap::new())),
+            conflict_policy: ToolConflictPolicy::RoundRobin,
+        }
+    }
+
+    /// Override the default keepalive/reconnect tuning used by the
+    /// background connection supervisor.
+    pub fn with_supervisor_config(mut self, supervisor: SupervisorConfig) -> Self {
+        self.supervisor = supervisor;
+        self
+    }
+
+    /// Override how `call_tool_routed` resolves a tool name that's
+    /// provided by more than one connected server.
+    pub fn with_conflict_policy(mut self, policy: ToolConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    /// Build a client directly from pre-built services, bypassing
+    /// `create_service_for_config` entirely. Servers with a matching entry
+    /// in `services` start `Connected`; any other enabled server in
+    /// `config` starts `Disconnected`, same as `initialize` before
+    /// `connect_all` runs. Intended for assembling a client from mocks in
+    /// tests.
+    pub fn with_services(
+        config: MCPConfig,
+        mut services: HashMap<String, Box<dyn MCPService>>,
+    ) -> Self {
+        let mut connections = IndexMap::new();
+        for (name, server_config) in config.enabled_servers() {
+            let service = services.remove(name);
+            let status = if service.is_some() {
+                ConnectionStatus::Connected
+            } else {
+                ConnectionStatus::Disconnected
+            };
+
+            connections.insert(
+                name.clone(),
+                MCPServerConnection {
+                    name: name.clone(),
+                    config: server_config.clone(),
+                    service,
+                    status,
+                    reconnecting: Arc::new(AtomicBool::new(false)),
+                },
+            );
+        }
+
+        Self {
+            connections: Arc::new(RwLock::new(connections)),
+            config,
+            supervisor: SupervisorConfig::default(),
+            supervisor_handle: None,
+            supervisor_shutdown: None,
+            tool_index: Arc::new(RwLock::new(HashMap::new())),
+            tool_cursors: Arc::new(RwLock::new(HashMap::new())),
+            conflict_policy: ToolConflictPolicy::RoundRobin,
         }
     }
 
@@ -105,71 +229,316 @@ impl MCPClient {
         self.config.validate()?;
 
         // Initialize each enabled server
-        for (name, server_config) in self.config.enabled_servers() {
-            let connection = MCPServerConnection {
-                name: name.clone(),
-                config: server_config.clone(),
-                service: None,
-                status: ConnectionStatus::Disconnected,
-            };
+        {
+            let mut connections = self.connections.write().await;
+            for (name, server_config) in self.config.enabled_servers() {
+                let connection = MCPServerConnection {
+                    name: name.clone(),
+                    config: server_config.clone(),
+                    service: None,
+                    status: ConnectionStatus::Disconnected,
+                    reconnecting: Arc::new(AtomicBool::new(false)),
+                };
 
-            self.connections.insert(name.clone(), connection);
+                connections.insert(name.clone(), connection);
+            }
         }
 
         // Connect to all servers concurrently
         self.connect_all().await?;
 
+        Self::refresh_tool_index(&self.connections, &self.tool_index).await;
+
+        self.spawn_supervisor();
+
         info!("MCP client initialization complete");
         Ok(())
     }
 
-    /// Connect to all configured servers
-    pub async fn connect_all(&mut self) -> Result<()> {
-        let mut connection_tasks = Vec::new();
-
-        for (name, connection) in &mut self.connections {
-            if matches!(connection.status, ConnectionStatus::Disconnected) {
-                connection.status = ConnectionStatus::Connecting;
+    /// Rebuild the tool-name -> hosting-servers index from the current set
+    /// of connected services, in connection order.
+    async fn refresh_tool_index(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        tool_index: &Arc<RwLock<HashMap<String, Vec<String>>>>,
+    ) {
+        let mut index: HashMap<String, Vec<String>> = HashMap::new();
 
-                let server_config = connection.config.clone();
-                let server_name = name.clone();
+        let connections = connections.read().await;
+        for (name, connection) in connections.iter() {
+            if let (Some(service), ConnectionStatus::Connected) =
+                (&connection.service, &connection.status)
+            {
+                match service.list_tools().await {
+                    Ok(tools) => {
+                        for tool in tools {
+                            index.entry(tool.name).or_default().push(name.clone());
+                        }
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Failed to list tools for server {} while refreshing tool index: {}",
+                            name, e
+                        );
+                    }
+                }
+            }
+        }
+        drop(connections);
 
-                let task = tokio::spawn(async move {
-                    Self::create_service_for_config(&server_name, &server_config).await
-                });
+        *tool_index.write().await = index;
+    }
 
-                connection_tasks.push((name.clone(), task));
+    /// Connect to all configured servers
+    pub async fn connect_all(&mut self) -> Result<()> {
+        let pending: Vec<(String, ServerConfig)> = {
+            let mut connections = self.connections.write().await;
+            let mut pending = Vec::new();
+            for (name, connection) in connections.iter_mut() {
+                if matches!(connection.status, ConnectionStatus::Disconnected) {
+                    connection.status = ConnectionStatus::Connecting;
+                    pending.push((name.clone(), connection.config.clone()));
+                }
             }
+            pending
+        };
+
+        let mut connection_tasks = Vec::new();
+        for (name, server_config) in pending {
+            let task = tokio::spawn(async move {
+                Self::create_service_for_config(&name, &server_config).await
+            });
+            connection_tasks.push((name, task));
         }
 
         // Wait for all connections to complete
         for (name, task) in connection_tasks {
-            match task.await {
-                Ok(Ok(service)) => {
-                    if let Some(connection) = self.connections.get_mut(&name) {
+            let outcome = match task.await {
+                Ok(outcome) => outcome,
+                Err(e) => Err(anyhow!("Task failed: {}", e)),
+            };
+
+            let mut connections = self.connections.write().await;
+            if let Some(connection) = connections.get_mut(&name) {
+                match outcome {
+                    Ok(service) => {
                         connection.service = Some(service);
                         connection.status = ConnectionStatus::Connected;
                         info!("Successfully connected to MCP server: {}", name);
                     }
-                }
-                Ok(Err(e)) => {
-                    if let Some(connection) = self.connections.get_mut(&name) {
+                    Err(e) => {
                         connection.status = ConnectionStatus::Failed(e.to_string());
                         error!("Failed to connect to MCP server {}: {}", name, e);
                     }
                 }
-                Err(e) => {
-                    if let Some(connection) = self.connections.get_mut(&name) {
-                        connection.status = ConnectionStatus::Failed(format!("Task failed: {}", e));
-                        error!("Connection task failed for {}: {}", name, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Spawn the background supervisor task that periodically health-checks
+    /// `Connected` servers and drives reconnection with exponential backoff
+    /// for servers that are `Failed` or fail their probe.
+    fn spawn_supervisor(&mut self) {
+        let connections = Arc::clone(&self.connections);
+        let tool_index = Arc::clone(&self.tool_index);
+        let supervisor = self.supervisor.clone();
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
+        self.supervisor_shutdown = Some(shutdown_tx);
+
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown_rx => {
+                        debug!("Connection supervisor shutting down");
+                        break;
+                    }
+                    _ = tokio::time::sleep(supervisor.ping_interval) => {
+                        Self::supervise_tick(&connections, &tool_index, &supervisor).await;
                     }
                 }
             }
+        });
+
+        self.supervisor_handle = Some(handle);
+    }
+
+    /// One supervisor pass: probe every connected server, and kick off a
+    /// backoff-guarded reconnect for any server that's `Failed` or just
+    /// failed its probe.
+    async fn supervise_tick(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        tool_index: &Arc<RwLock<HashMap<String, Vec<String>>>>,
+        supervisor: &SupervisorConfig,
+    ) {
+        let targets: Vec<(String, ConnectionStatus)> = {
+            let guard = connections.read().await;
+            guard
+                .iter()
+                .map(|(name, conn)| (name.clone(), conn.status.clone()))
+                .collect()
+        };
+
+        for (name, status) in targets {
+            match status {
+                ConnectionStatus::Connected => {
+                    if let Err(e) = Self::probe(connections, &name).await {
+                        warn!("Health probe failed for server {}: {}", name, e);
+                        Self::mark_failed(connections, &name, &e.to_string()).await;
+                        Self::spawn_reconnect_with_backoff(
+                            Arc::clone(connections),
+                            Arc::clone(tool_index),
+                            name,
+                            supervisor.clone(),
+                        );
+                    }
+                }
+                ConnectionStatus::Failed(_) => {
+                    Self::spawn_reconnect_with_backoff(
+                        Arc::clone(connections),
+                        Arc::clone(tool_index),
+                        name,
+                        supervisor.clone(),
+                    );
+                }
+                _ => {}
+            }
         }
+    }
 
+    /// A cheap round-trip used as a keepalive/health probe for a connected
+    /// server.
+    async fn probe(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        name: &str,
+    ) -> Result<()> {
+        let guard = connections.read().await;
+        let connection = guard
+            .get(name)
+            .ok_or_else(|| anyhow!("Server '{}' not found", name))?;
+        let service = connection
+            .service
+            .as_ref()
+            .ok_or_else(|| anyhow!("Server '{}' has no service", name))?;
+
+        service.list_tools().await?;
         Ok(())
     }
 
+    async fn mark_failed(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        name: &str,
+        reason: &str,
+    ) {
+        let mut guard = connections.write().await;
+        if let Some(connection) = guard.get_mut(name) {
+            connection.service = None;
+            connection.status = ConnectionStatus::Failed(reason.to_string());
+        }
+    }
+
+    /// Spawn a guarded, backoff-paced reconnect loop for `name`. Skips
+    /// entirely if a reconnect for this server is already in flight.
+    fn spawn_reconnect_with_backoff(
+        connections: Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        tool_index: Arc<RwLock<HashMap<String, Vec<String>>>>,
+        name: String,
+        supervisor: SupervisorConfig,
+    ) {
+        tokio::spawn(async move {
+            let in_flight = {
+                let guard = connections.read().await;
+                match guard.get(&name) {
+                    Some(connection) => Arc::clone(&connection.reconnecting),
+                    None => return,
+                }
+            };
+
+            if in_flight
+                .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                .is_err()
+            {
+                debug!("Reconnect already in progress for server {}, skipping", name);
+                return;
+            }
+
+            let mut delay = supervisor.base_delay;
+            for attempt in 1..=supervisor.max_attempts {
+                {
+                    let mut guard = connections.write().await;
+                    if let Some(connection) = guard.get_mut(&name) {
+                        connection.status = ConnectionStatus::Reconnecting { attempt };
+                    }
+                }
+
+                match Self::attempt_reconnect(&connections, &tool_index, &name).await {
+                    Ok(()) => {
+                        info!("Reconnected to server {} on attempt {}", name, attempt);
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Reconnect attempt {}/{} for server {} failed: {}",
+                            attempt, supervisor.max_attempts, name, e
+                        );
+                        if attempt == supervisor.max_attempts {
+                            let mut guard = connections.write().await;
+                            if let Some(connection) = guard.get_mut(&name) {
+                                connection.status = ConnectionStatus::Failed(e.to_string());
+                            }
+                            break;
+                        }
+                        tokio::time::sleep(delay).await;
+                        delay = (delay * 2).min(supervisor.max_delay);
+                    }
+                }
+            }
+
+            in_flight.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Tear down and recreate the service for `server_name`, updating its
+    /// status on success or failure. Shared by the public `reconnect_server`
+    /// and the supervisor's backoff loop.
+    async fn attempt_reconnect(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        tool_index: &Arc<RwLock<HashMap<String, Vec<String>>>>,
+        server_name: &str,
+    ) -> Result<()> {
+        let (config, old_service) = {
+            let mut guard = connections.write().await;
+            let connection = guard
+                .get_mut(server_name)
+                .ok_or_else(|| anyhow!("Server '{}' not found", server_name))?;
+            (connection.config.clone(), connection.service.take())
+        };
+
+        if let Some(mut service) = old_service {
+            let _ = service.disconnect().await;
+        }
+
+        match Self::create_service_for_config(server_name, &config).await {
+            Ok(service) => {
+                let mut guard = connections.write().await;
+                if let Some(connection) = guard.get_mut(server_name) {
+                    connection.service = Some(service);
+                    connection.status = ConnectionStatus::Connected;
+                }
+                drop(guard);
+                Self::refresh_tool_index(connections, tool_index).await;
+                Ok(())
+            }
+            Err(e) => {
+                let mut guard = connections.write().await;
+                if let Some(connection) = guard.get_mut(server_name) {
+                    connection.status = ConnectionStatus::Failed(e.to_string());
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// Create a service for a given server configuration
     async fn create_service_for_config(
         name: &str,
@@ -208,22 +577,128 @@ impl MCPClient {
 
                 Ok(Box::new(service))
             }
-            ServerConfig::Remote { url, .. } => {
+            ServerConfig::Remote {
+                url,
+                headers,
+                timeout_ms,
+                auth,
+                ..
+            } => {
                 debug!("Creating remote MCP service for {}: {}", name, url);
 
-                // TODO: Implement HTTP/SSE transport for remote servers
-                // This would use rmcp's HTTP transport capabilities
+                let mut merged_headers = headers.clone().unwrap_or_default();
+                let authenticator = crate::auth::authenticator_for(auth, &crate::config::ProcessEnv);
+                merged_headers.extend(authenticator.headers());
+
+                let timeout = timeout_ms.map(std::time::Duration::from_millis);
+                let service = RemoteMCPService::new(url, &merged_headers, timeout).await?;
 
-                Err(anyhow!("Remote MCP servers not yet implemented"))
+                Ok(Box::new(service))
+            }
+            ServerConfig::Ssh {
+                host,
+                command,
+                args,
+                env,
+                remote_binary,
+                ..
+            } => {
+                debug!(
+                    "Creating SSH MCP service for {} on {}: {} {:?}",
+                    name, host, command, args
+                );
+
+                let remote_command = if let Some(local_binary) = remote_binary {
+                    Self::ensure_remote_binary(host, local_binary).await?
+                } else {
+                    command.clone()
+                };
+
+                let mut remote_cmd_line = String::new();
+                if let Some(env_vars) = env {
+                    for (key, value) in env_vars {
+                        remote_cmd_line.push_str(&format!("{}={} ", key, shell_escape(value)));
+                    }
+                }
+                remote_cmd_line.push_str(&shell_escape(&remote_command));
+                if let Some(args) = args {
+                    for arg in args {
+                        remote_cmd_line.push(' ');
+                        remote_cmd_line.push_str(&shell_escape(arg));
+                    }
+                }
+
+                let mut cmd = Command::new("ssh");
+                cmd.arg(host).arg(remote_cmd_line);
+
+                cmd.stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped());
+
+                let transport = TokioChildProcess::new(cmd)?;
+                let service = LocalMCPService::new(transport).await?;
+
+                Ok(Box::new(service))
             }
         }
     }
 
+    /// Upload `local_binary` to `host` if a matching content hash isn't
+    /// already cached there, returning the path to the (now) remote binary.
+    async fn ensure_remote_binary(host: &str, local_binary: &str) -> Result<String> {
+        let contents = tokio::fs::read(Path::new(local_binary))
+            .await
+            .map_err(|e| anyhow!("Failed to read remote_binary '{}': {}", local_binary, e))?;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        contents.hash(&mut hasher);
+        let digest = format!("{:016x}", hasher.finish());
+
+        let remote_dir = "~/.cache/rusty-mcp/bin";
+        let remote_path = format!("{}/{}", remote_dir, digest);
+
+        let check = Command::new("ssh")
+            .arg(host)
+            .arg(format!("test -f {} && echo present", remote_path))
+            .output()
+            .await?;
+
+        if String::from_utf8_lossy(&check.stdout).trim() == "present" {
+            debug!("Remote binary for {} already cached at {}", host, remote_path);
+            return Ok(remote_path);
+        }
+
+        Command::new("ssh")
+            .arg(host)
+            .arg(format!("mkdir -p {}", remote_dir))
+            .status()
+            .await?;
+
+        let status = Command::new("scp")
+            .arg(local_binary)
+            .arg(format!("{}:{}", host, remote_path))
+            .status()
+            .await?;
+
+        if !status.success() {
+            return Err(anyhow!("Failed to upload remote_binary to {}", host));
+        }
+
+        Command::new("ssh")
+            .arg(host)
+            .arg(format!("chmod +x {}", remote_path))
+            .status()
+            .await?;
+
+        Ok(remote_path)
+    }
+
     /// Get all available tools from all connected servers
     pub async fn list_all_tools(&self) -> Result<HashMap<String, Vec<Tool>>> {
         let mut all_tools = HashMap::new();
 
-        for (name, connection) in &self.connections {
+        let connections = self.connections.read().await;
+        for (name, connection) in connections.iter() {
             if let (Some(service), ConnectionStatus::Connected) =
                 (&connection.service, &connection.status)
             {
@@ -249,8 +724,17 @@ impl MCPClient {
         tool_name: &str,
         arguments: Option<Value>,
     ) -> Result<ToolResult> {
-        let connection = self
-            .connections
+        Self::call_tool_locked(&self.connections, server_name, tool_name, arguments).await
+    }
+
+    async fn call_tool_locked(
+        connections: &Arc<RwLock<IndexMap<String, MCPServerConnection>>>,
+        server_name: &str,
+        tool_name: &str,
+        arguments: Option<Value>,
+    ) -> Result<ToolResult> {
+        let connections = connections.read().await;
+        let connection = connections
             .get(server_name)
             .ok_or_else(|| anyhow!("Server '{}' not found", server_name))?;
 
@@ -266,11 +750,132 @@ impl MCPClient {
         service.call_tool(tool_name, arguments).await
     }
 
+    /// Dispatch a batch of tool calls. By default all calls run concurrently
+    /// (via `tokio::spawn` + `futures::future::join_all`) and results are
+    /// returned in the same order as `calls`, regardless of completion
+    /// order. When `sequential` is set, calls run strictly in order on the
+    /// caller's task and the batch stops at the first error, which is
+    /// useful when a later call depends on an earlier one's side effects.
+    pub async fn call_tools_batch(
+        &self,
+        calls: Vec<(String, String, Option<Value>)>,
+        sequential: bool,
+    ) -> Vec<Result<ToolResult>> {
+        if sequential {
+            let mut results = Vec::with_capacity(calls.len());
+            for (server_name, tool_name, arguments) in calls {
+                let result = self.call_tool(&server_name, &tool_name, arguments).await;
+                let failed = result.is_err();
+                results.push(result);
+                if failed {
+                    break;
+                }
+            }
+            return results;
+        }
+
+        let tasks: Vec<_> = calls
+            .into_iter()
+            .map(|(server_name, tool_name, arguments)| {
+                let connections = Arc::clone(&self.connections);
+                tokio::spawn(async move {
+                    Self::call_tool_locked(&connections, &server_name, &tool_name, arguments).await
+                })
+            })
+            .collect();
+
+        futures::future::join_all(tasks)
+            .await
+            .into_iter()
+            .map(|outcome| match outcome {
+                Ok(result) => result,
+                Err(e) => Err(anyhow!("Batch tool call task failed: {}", e)),
+            })
+            .collect()
+    }
+
+    /// Call `tool_name` without knowing which server hosts it, using the
+    /// tool index built by `refresh_tool_index`. When more than one
+    /// connected server exposes the same tool name, behavior follows
+    /// `conflict_policy`; under `RoundRobin`, a failed call transparently
+    /// fails over to the next healthy server before an error is surfaced.
+    pub async fn call_tool_routed(
+        &self,
+        tool_name: &str,
+        arguments: Option<Value>,
+    ) -> Result<ToolResult> {
+        let servers = {
+            let index = self.tool_index.read().await;
+            index.get(tool_name).cloned().unwrap_or_default()
+        };
+
+        if servers.is_empty() {
+            return Err(anyhow!("No connected server exposes tool '{}'", tool_name));
+        }
+
+        if servers.len() > 1 {
+            match self.conflict_policy {
+                ToolConflictPolicy::Error => {
+                    return Err(anyhow!(
+                        "Tool '{}' is provided by multiple servers ({}); choose a conflict policy other than Error to route automatically",
+                        tool_name,
+                        servers.join(", ")
+                    ));
+                }
+                ToolConflictPolicy::FirstWins => {
+                    return self.call_tool(&servers[0], tool_name, arguments).await;
+                }
+                ToolConflictPolicy::RoundRobin => {}
+            }
+        }
+
+        let cursor = self.cursor_for(tool_name).await;
+        let start = cursor.fetch_add(1, Ordering::SeqCst) % servers.len();
+
+        let mut last_err = None;
+        for offset in 0..servers.len() {
+            let server_name = &servers[(start + offset) % servers.len()];
+            match self
+                .call_tool(server_name, tool_name, arguments.clone())
+                .await
+            {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    warn!(
+                        "Routed call to '{}' on server '{}' failed, trying next: {}",
+                        tool_name, server_name, e
+                    );
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow!("No healthy server available for tool '{}'", tool_name)))
+    }
+
+    /// Get (creating if absent) the round-robin cursor for `tool_name`.
+    async fn cursor_for(&self, tool_name: &str) -> Arc<AtomicUsize> {
+        {
+            let cursors = self.tool_cursors.read().await;
+            if let Some(cursor) = cursors.get(tool_name) {
+                return Arc::clone(cursor);
+            }
+        }
+
+        let mut cursors = self.tool_cursors.write().await;
+        Arc::clone(
+            cursors
+                .entry(tool_name.to_string())
+                .or_insert_with(|| Arc::new(AtomicUsize::new(0))),
+        )
+    }
+
     /// Get all available resources from all connected servers
     pub async fn list_all_resources(&self) -> Result<HashMap<String, Vec<Resource>>> {
         let mut all_resources = HashMap::new();
 
-        for (name, connection) in &self.connections {
+        let connections = self.connections.read().await;
+        for (name, connection) in connections.iter() {
             if let (Some(service), ConnectionStatus::Connected) =
                 (&connection.service, &connection.status)
             {
@@ -295,8 +900,8 @@ impl MCPClient {
         server_name: &str,
         resource_uri: &str,
     ) -> Result<ResourceContent> {
-        let connection = self
-            .connections
+        let connections = self.connections.read().await;
+        let connection = connections
             .get(server_name)
             .ok_or_else(|| anyhow!("Server '{}' not found", server_name))?;
 
@@ -313,46 +918,45 @@ impl MCPClient {
     }
 
     /// Get connection status for all servers
-    pub fn get_server_status(&self) -> HashMap<String, ConnectionStatus> {
-        self.connections
+    pub async fn get_server_status(&self) -> HashMap<String, ConnectionStatus> {
+        let connections = self.connections.read().await;
+        connections
             .iter()
             .map(|(name, conn)| (name.clone(), conn.status.clone()))
             .collect()
     }
 
-    /// Reconnect to a specific server
-    pub async fn reconnect_server(&mut self, server_name: &str) -> Result<()> {
-        let connection = self
-            .connections
-            .get_mut(server_name)
-            .ok_or_else(|| anyhow!("Server '{}' not found", server_name))?;
-
-        // Disconnect existing service if any
-        if let Some(mut service) = connection.service.take() {
-            let _ = service.disconnect().await;
+    /// Reconnect to a specific server, bypassing the supervisor's backoff
+    /// schedule for an immediate, user-requested attempt.
+    pub async fn reconnect_server(&self, server_name: &str) -> Result<()> {
+        {
+            let mut connections = self.connections.write().await;
+            let connection = connections
+                .get_mut(server_name)
+                .ok_or_else(|| anyhow!("Server '{}' not found", server_name))?;
+            connection.status = ConnectionStatus::Connecting;
         }
 
-        connection.status = ConnectionStatus::Connecting;
-
-        match Self::create_service_for_config(server_name, &connection.config).await {
-            Ok(service) => {
-                connection.service = Some(service);
-                connection.status = ConnectionStatus::Connected;
-                info!("Successfully reconnected to server: {}", server_name);
-                Ok(())
-            }
-            Err(e) => {
-                connection.status = ConnectionStatus::Failed(e.to_string());
-                Err(e)
-            }
+        let result = Self::attempt_reconnect(&self.connections, &self.tool_index, server_name).await;
+        if result.is_ok() {
+            info!("Successfully reconnected to server: {}", server_name);
         }
+        result
     }
 
     /// Shutdown all connections
     pub async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down MCP client");
 
-        for (name, connection) in &mut self.connections {
+        if let Some(shutdown_tx) = self.supervisor_shutdown.take() {
+            let _ = shutdown_tx.send(());
+        }
+        if let Some(handle) = self.supervisor_handle.take() {
+            let _ = handle.await;
+        }
+
+        let mut connections = self.connections.write().await;
+        for (name, connection) in connections.iter_mut() {
             if let Some(mut service) = connection.service.take() {
                 if let Err(e) = service.disconnect().await {
                     warn!("Error disconnecting from server {}: {}", name, e);
@@ -380,86 +984,89 @@ impl LocalMCPService {
 #[async_trait::async_trait]
 impl MCPService for LocalMCPService {
     async fn list_tools(&self) -> Result<Vec<Tool>> {
+        let response = self.service.list_tools(None).await?;
+        Ok(tools_from_response(response))
+    }
+
+    async fn call_tool(&self, name: &str, arguments: Option<Value>) -> Result<ToolResult> {
+        let response = self.service.call_tool(call_tool_param(name, arguments)).await?;
+        Ok(tool_result_from_response(response))
+    }
+
+    async fn list_resources(&self) -> Result<Vec<Resource>> {
+        let response = self.service.list_resources(None).await?;
+        Ok(resources_from_response(response))
+    }
+
+    async fn read_resource(&self, uri: &str) -> Result<ResourceContent> {
         let response = self
             .service
-            .list_tools(None)
+            .read_resource(rmcp::model::ReadResourceRequestParam {
+                uri: uri.to_string(),
+            })
             .await?;
 
-        let tools = response
-            .tools
-            .into_iter()
-            .map(|tool| Tool {
-                name: tool.name.to_string(),
-                description: tool.description.map(|d| d.to_string()),
-                input_schema: Some(serde_json::Value::Object((*tool.input_schema).clone())),
-            })
-            .collect();
+        Ok(resource_content_from_response(uri, response))
+    }
 
-        Ok(tools)
+    async fn disconnect(&mut self) -> Result<()> {
+        // The rmcp service will handle cleanup automatically when dropped
+        Ok(())
     }
+}
 
-    async fn call_tool(&self, name: &str, arguments: Option<Value>) -> Result<ToolResult> {
-        let param = CallToolRequestParam {
-            name: name.to_string().into(),
-            arguments: arguments.and_then(|v| v.as_object().cloned()),
-        };
+impl Drop for LocalMCPService {
+    fn drop(&mut self) {
+        // rmcp handles cleanup automatically
+    }
+}
 
-        let response = self.service.call_tool(param).await?;
+/// Implementation of MCPService for remote servers over HTTP with
+/// Server-Sent Events for the server->client stream, using rmcp's
+/// streamable HTTP/SSE client transport.
+struct RemoteMCPService {
+    service: rmcp::service::RunningService<rmcp::service::RoleClient, ()>,
+}
 
-        let content = response
-            .content
-            .into_iter()
-            .map(|content| {
-                let (content_type, text, data) = match &content.raw {
-                    rmcp::model::RawContent::Text(text_content) => {
-                        ("text".to_string(), Some(text_content.text.clone()), None)
-                    }
-                    rmcp::model::RawContent::Image(image_content) => {
-                        ("image".to_string(), None, Some(serde_json::json!({
-                            "data": image_content.data,
-                            "mime_type": image_content.mime_type
-                        })))
-                    }
-                    rmcp::model::RawContent::Resource(resource) => {
-                        ("resource".to_string(), None, Some(serde_json::to_value(resource).unwrap_or_default()))
-                    }
-                    rmcp::model::RawContent::Audio(audio) => {
-                        ("audio".to_string(), None, Some(serde_json::to_value(audio).unwrap_or_default()))
-                    }
-                };
+impl RemoteMCPService {
+    async fn new(
+        url: &str,
+        headers: &HashMap<String, String>,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Self> {
+        let mut config = rmcp::transport::sse_client::SseClientConfig {
+            url: url.to_string(),
+            ..Default::default()
+        };
+        config.headers.extend(headers.clone());
+        if let Some(timeout) = timeout {
+            config.timeout = Some(timeout);
+        }
 
-                ToolResultContent {
-                    content_type,
-                    text,
-                    data,
-                }
-            })
-            .collect();
+        let transport = rmcp::transport::sse_client::SseClientTransport::start(config)
+            .await
+            .map_err(|e| anyhow!("Failed to start SSE transport for {}: {}", url, e))?;
 
-        Ok(ToolResult {
-            content,
-            is_error: response.is_error.unwrap_or(false),
-        })
+        let service = ().serve(transport).await?;
+        Ok(Self { service })
     }
+}
 
-    async fn list_resources(&self) -> Result<Vec<Resource>> {
-        let response = self
-            .service
-            .list_resources(None)
-            .await?;
+#[async_trait::async_trait]
+impl MCPService for RemoteMCPService {
+    async fn list_tools(&self) -> Result<Vec<Tool>> {
+        let response = self.service.list_tools(None).await?;
+        Ok(tools_from_response(response))
+    }
 
-        let resources = response
-            .resources
-            .into_iter()
-            .map(|resource| Resource {
-                uri: resource.raw.uri.clone(),
-                name: resource.raw.name.clone(),
-                description: resource.raw.description.clone(),
-                mime_type: resource.raw.mime_type.clone(),
-            })
-            .collect();
+    async fn call_tool(&self, name: &str, arguments: Option<Value>) -> Result<ToolResult> {
+        let response = self.service.call_tool(call_tool_param(name, arguments)).await?;
+        Ok(tool_result_from_response(response))
+    }
 
-        Ok(resources)
+    async fn list_resources(&self) -> Result<Vec<Resource>> {
+        let response = self.service.list_resources(None).await?;
+        Ok(resources_from_response(response))
     }
 
     async fn read_resource(&self, uri: &str) -> Result<ResourceContent> {
@@ -470,25 +1077,7 @@ impl MCPService for LocalMCPService {
             })
             .await?;
 
-        // Handle different content types
-        let (text, blob) = if let Some(contents) = response.contents.first() {
-            match contents {
-                rmcp::model::ResourceContents::TextResourceContents { text, .. } => (Some(text.clone()), None),
-                rmcp::model::ResourceContents::BlobResourceContents { blob, .. } => (None, Some(blob.clone())),
-            }
-        } else {
-            (None, None)
-        };
-
-        Ok(ResourceContent {
-            uri: uri.to_string(),
-            mime_type: response.contents.first().and_then(|c| match c {
-                rmcp::model::ResourceContents::TextResourceContents { mime_type, .. } => mime_type.clone(),
-                rmcp::model::ResourceContents::BlobResourceContents { mime_type, .. } => mime_type.clone(),
-            }),
-            text,
-            blob,
-        })
+        Ok(resource_content_from_response(uri, response))
     }
 
     async fn disconnect(&mut self) -> Result<()> {
@@ -497,8 +1086,126 @@ impl MCPService for LocalMCPService {
     }
 }
 
-impl Drop for LocalMCPService {
+impl Drop for RemoteMCPService {
     fn drop(&mut self) {
         // rmcp handles cleanup automatically
     }
 }
+
+/// Build the rmcp call-tool parameter shared by every `MCPService` impl.
+fn call_tool_param(name: &str, arguments: Option<Value>) -> CallToolRequestParam {
+    CallToolRequestParam {
+        name: name.to_string().into(),
+        arguments: arguments.and_then(|v| v.as_object().cloned()),
+    }
+}
+
+/// Convert an rmcp tool list response into our `Tool` type.
+fn tools_from_response(response: rmcp::model::ListToolsResult) -> Vec<Tool> {
+    response
+        .tools
+        .into_iter()
+        .map(|tool| Tool {
+            name: tool.name.to_string(),
+            description: tool.description.map(|d| d.to_string()),
+            input_schema: Some(serde_json::Value::Object((*tool.input_schema).clone())),
+        })
+        .collect()
+}
+
+/// Convert an rmcp call-tool response into our `ToolResult` type.
+fn tool_result_from_response(response: rmcp::model::CallToolResult) -> ToolResult {
+    let content = response
+        .content
+        .into_iter()
+        .map(|content| {
+            let (content_type, text, data) = match &content.raw {
+                rmcp::model::RawContent::Text(text_content) => {
+                    ("text".to_string(), Some(text_content.text.clone()), None)
+                }
+                rmcp::model::RawContent::Image(image_content) => (
+                    "image".to_string(),
+                    None,
+                    Some(serde_json::json!({
+                        "data": image_content.data,
+                        "mime_type": image_content.mime_type
+                    })),
+                ),
+                rmcp::model::RawContent::Resource(resource) => (
+                    "resource".to_string(),
+                    None,
+                    Some(serde_json::to_value(resource).unwrap_or_default()),
+                ),
+                rmcp::model::RawContent::Audio(audio) => (
+                    "audio".to_string(),
+                    None,
+                    Some(serde_json::to_value(audio).unwrap_or_default()),
+                ),
+            };
+
+            ToolResultContent {
+                content_type,
+                text,
+                data,
+            }
+        })
+        .collect();
+
+    ToolResult {
+        content,
+        is_error: response.is_error.unwrap_or(false),
+    }
+}
+
+/// Convert an rmcp resource list response into our `Resource` type.
+fn resources_from_response(response: rmcp::model::ListResourcesResult) -> Vec<Resource> {
+    response
+        .resources
+        .into_iter()
+        .map(|resource| Resource {
+            uri: resource.raw.uri.clone(),
+            name: resource.raw.name.clone(),
+            description: resource.raw.description.clone(),
+            mime_type: resource.raw.mime_type.clone(),
+        })
+        .collect()
+}
+
+/// Convert an rmcp read-resource response into our `ResourceContent` type.
+fn resource_content_from_response(
+    uri: &str,
+    response: rmcp::model::ReadResourceResult,
+) -> ResourceContent {
+    let (text, blob) = if let Some(contents) = response.contents.first() {
+        match contents {
+            rmcp::model::ResourceContents::TextResourceContents { text, .. } => {
+                (Some(text.clone()), None)
+            }
+            rmcp::model::ResourceContents::BlobResourceContents { blob, .. } => {
+                (None, Some(blob.clone()))
+            }
+        }
+    } else {
+        (None, None)
+    };
+
+    ResourceContent {
+        uri: uri.to_string(),
+        mime_type: response.contents.first().and_then(|c| match c {
+            rmcp::model::ResourceContents::TextResourceContents { mime_type, .. } => {
+                mime_type.clone()
+            }
+            rmcp::model::ResourceContents::BlobResourceContents { mime_type, .. } => {
+                mime_type.clone()
+            }
+        }),
+        text,
+        blob,
+    }
+}
+
+/// Single-quote a value for safe inclusion in the remote shell command line
+/// built for the `Ssh` transport.
+fn shell_escape(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
@@ -6,6 +6,9 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::time::Duration;
+
+use crate::fuzzy;
 
 /// Position in a text document (0-indexed)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,6 +49,12 @@ pub struct Completion {
     pub confidence: f32,
     /// Source of the completion ("static", "llm", "mcp", etc.)
     pub source: CompletionSource,
+    /// Column span on `current_line` that `text` replaces, rather than
+    /// being inserted at the cursor. Used by completions that rewrite text
+    /// already typed (e.g. postfix templates turning `foo.if` into
+    /// `if foo { ... }`); `None` means the usual insert-at-cursor behavior.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace_range: Option<(usize, usize)>,
     /// Optional metadata about how this completion was generated
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
@@ -63,6 +72,8 @@ pub enum CompletionSource {
     Mcp,
     /// RAG-enhanced completion
     Rag,
+    /// Derived from a compiler/linter diagnostic (e.g. `cargo check`, clippy)
+    Diagnostic,
 }
 
 impl fmt::Display for CompletionSource {
@@ -72,6 +83,7 @@ impl fmt::Display for CompletionSource {
             CompletionSource::Llm => write!(f, "llm"),
             CompletionSource::Mcp => write!(f, "mcp"),
             CompletionSource::Rag => write!(f, "rag"),
+            CompletionSource::Diagnostic => write!(f, "diagnostic"),
         }
     }
 }
@@ -86,7 +98,8 @@ pub struct CompletionResponse {
 }
 
 /// Pattern types that can be detected in code
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Pattern {
     /// Function definition start (e.g., "fn foo" or "function myFunc")
     FunctionStart,
@@ -102,6 +115,16 @@ pub enum Pattern {
     ImplBlock,
     /// Match/switch statement
     MatchStatement,
+    /// A postfix-completion trigger, e.g. `foo.if` or `result.ok`. `keyword`
+    /// is the identifier after the dot (`"if"`, `"ok"`, ...).
+    Postfix { keyword: String },
+    /// A formatting-macro call containing `{expr}`-style inline captures to
+    /// expand into positional `{}` placeholders plus trailing arguments,
+    /// e.g. `println!("{x}")` -> `println!("{}", x)`.
+    FormatLike,
+    /// Cursor sits inside an `impl <trait_name> for <Type> { ... }` block
+    /// missing one or more of the trait's required methods.
+    TraitImpl { trait_name: String },
     /// Unknown/no pattern detected
     Unknown,
 }
@@ -130,16 +153,52 @@ pub trait CompletionProvider: Send + Sync {
     }
 }
 
+/// Tuning knobs for how [`CompletionEngine::get_completions`] runs providers
+/// concurrently.
+#[derive(Debug, Clone, Copy)]
+pub struct CompletionEngineConfig {
+    /// Per-provider timeout. A provider that takes longer than this
+    /// contributes no completions instead of blocking the others.
+    pub provider_timeout: Duration,
+    /// Overall deadline for the whole `get_completions` call, so interactive
+    /// completions stay responsive even if every provider is individually
+    /// within its own `provider_timeout`.
+    pub overall_deadline: Duration,
+    /// Weight given to the fuzzy match score against `confidence` when
+    /// ranking results: `weight * fuzzy + (1 - weight) * confidence`.
+    pub fuzzy_weight: f32,
+}
+
+impl Default for CompletionEngineConfig {
+    fn default() -> Self {
+        Self {
+            provider_timeout: Duration::from_millis(500),
+            overall_deadline: Duration::from_secs(2),
+            fuzzy_weight: 0.6,
+        }
+    }
+}
+
 /// Main completion engine that orchestrates different providers
 pub struct CompletionEngine {
     providers: Vec<Box<dyn CompletionProvider>>,
+    config: CompletionEngineConfig,
 }
 
 impl CompletionEngine {
-    /// Create a new completion engine
+    /// Create a new completion engine with default timeouts
     pub fn new() -> Self {
         Self {
             providers: Vec::new(),
+            config: CompletionEngineConfig::default(),
+        }
+    }
+
+    /// Create a new completion engine with custom provider/overall timeouts
+    pub fn with_config(config: CompletionEngineConfig) -> Self {
+        Self {
+            providers: Vec::new(),
+            config,
         }
     }
 
@@ -148,14 +207,128 @@ impl CompletionEngine {
         self.providers.push(provider);
     }
 
+    /// Rank `completions` against the prefix the user has typed so far
+    /// (extracted from `request.current_line` at `request.cursor_position`),
+    /// blending each candidate's fuzzy match score with its `confidence`,
+    /// then merge texts that collapse to the same candidate, keeping the
+    /// higher-scoring one.
+    ///
+    /// Completions with a `replace_range` rewrite an already-typed span
+    /// (e.g. a postfix template turning `foo.ref` into `&foo`) rather than
+    /// continuing it, so `completion.text` is never a fuzzy subsequence of
+    /// the prefix; those are ranked on `confidence` alone instead of being
+    /// fuzzy-filtered out.
+    fn rank(&self, request: &CompletionRequest, completions: Vec<Completion>) -> Vec<Completion> {
+        let prefix = fuzzy::prefix_at(&request.current_line, request.cursor_position.column);
+
+        let mut scored: Vec<(f32, Completion)> = completions
+            .into_iter()
+            .filter_map(|completion| {
+                let fuzzy_score = if completion.replace_range.is_some() {
+                    1.0
+                } else {
+                    fuzzy::score(&prefix, &completion.text)?
+                };
+                let blended = fuzzy::blended_score(fuzzy_score, completion.confidence, self.config.fuzzy_weight);
+                Some((blended, completion))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        // `Vec::dedup_by` only collapses *adjacent* equals; two same-text
+        // entries with a differently-scored candidate sorted between them
+        // would otherwise both survive. Walk the score-sorted vec and keep
+        // only each text's first (highest-scoring) occurrence instead.
+        let mut seen_texts = std::collections::HashSet::new();
+        scored.retain(|(_, completion)| seen_texts.insert(completion.text.clone()));
+
+        scored.into_iter().map(|(_, completion)| completion).collect()
+    }
+
     /// Get completions for the given request
     ///
-    /// This will try all enabled providers and combine their results
+    /// Every enabled provider is polled concurrently, each wrapped in
+    /// `config.provider_timeout` so a hung provider (e.g. a slow LLM or MCP
+    /// call) contributes nothing instead of stalling fast local providers.
+    /// The whole call is additionally bounded by `config.overall_deadline`.
+    /// Once every future resolves, merged results are ranked by `rank`,
+    /// which blends each candidate's fuzzy match score against the typed
+    /// prefix with its `confidence`.
     pub async fn get_completions(&self, request: &CompletionRequest) -> Result<CompletionResponse> {
         let start = std::time::Instant::now();
+
+        let provider_futures = self.providers.iter().filter(|p| p.is_enabled()).map(|provider| {
+            let provider_timeout = self.config.provider_timeout;
+            async move {
+                match tokio::time::timeout(provider_timeout, provider.complete(request)).await {
+                    Ok(Ok(completions)) => {
+                        tracing::debug!(
+                            "Provider '{}' returned {} completions",
+                            provider.name(),
+                            completions.len()
+                        );
+                        completions
+                    }
+                    Ok(Err(e)) => {
+                        tracing::warn!("Provider '{}' failed: {}", provider.name(), e);
+                        Vec::new()
+                    }
+                    Err(_) => {
+                        tracing::warn!(
+                            "Provider '{}' timed out after {:?}",
+                            provider.name(),
+                            provider_timeout
+                        );
+                        Vec::new()
+                    }
+                }
+            }
+        });
+
+        let mut all_completions = match tokio::time::timeout(
+            self.config.overall_deadline,
+            futures::future::join_all(provider_futures),
+        )
+        .await
+        {
+            Ok(results) => results.into_iter().flatten().collect::<Vec<_>>(),
+            Err(_) => {
+                tracing::warn!(
+                    "get_completions exceeded overall deadline of {:?}",
+                    self.config.overall_deadline
+                );
+                Vec::new()
+            }
+        };
+
+        let all_completions = self.rank(request, all_completions);
+
+        let processing_time_ms = start.elapsed().as_millis() as u64;
+
+        Ok(CompletionResponse {
+            completions: all_completions,
+            processing_time_ms,
+        })
+    }
+
+    /// Like `get_completions`, but invokes `on_partial` with each provider's
+    /// completions as soon as that provider returns, instead of waiting for
+    /// every provider to finish before the caller sees anything. Providers
+    /// are polled one after another, without `config.provider_timeout` or
+    /// `config.overall_deadline`. Used by `JsonRpcServer`'s
+    /// `subscribe_completion` to push incremental results.
+    pub async fn stream_completions<F>(
+        &self,
+        request: &CompletionRequest,
+        mut on_partial: F,
+    ) -> Result<CompletionResponse>
+    where
+        F: FnMut(&[Completion]) + Send,
+    {
+        let start = std::time::Instant::now();
         let mut all_completions = Vec::new();
 
-        // Try each provider
         for provider in &self.providers {
             if !provider.is_enabled() {
                 continue;
@@ -168,31 +341,22 @@ impl CompletionEngine {
                         provider.name(),
                         completions.len()
                     );
+                    if !completions.is_empty() {
+                        on_partial(&completions);
+                    }
                     all_completions.extend(completions);
                 }
                 Err(e) => {
-                    tracing::warn!(
-                        "Provider '{}' failed: {}",
-                        provider.name(),
-                        e
-                    );
+                    tracing::warn!("Provider '{}' failed: {}", provider.name(), e);
                 }
             }
         }
 
-        // Sort by confidence (highest first) and deduplicate
-        all_completions.sort_by(|a, b| {
-            b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        // Remove duplicates (keep highest confidence)
-        all_completions.dedup_by(|a, b| a.text == b.text);
-
-        let processing_time_ms = start.elapsed().as_millis() as u64;
+        let all_completions = self.rank(request, all_completions);
 
         Ok(CompletionResponse {
             completions: all_completions,
-            processing_time_ms,
+            processing_time_ms: start.elapsed().as_millis() as u64,
         })
     }
 }
@@ -219,4 +383,197 @@ mod tests {
         assert_eq!(CompletionSource::Static.to_string(), "static");
         assert_eq!(CompletionSource::Llm.to_string(), "llm");
     }
+
+    struct DelayedProvider {
+        name: &'static str,
+        delay: Duration,
+        confidence: f32,
+    }
+
+    #[async_trait::async_trait]
+    impl CompletionProvider for DelayedProvider {
+        async fn complete(&self, _request: &CompletionRequest) -> Result<Vec<Completion>> {
+            tokio::time::sleep(self.delay).await;
+            Ok(vec![Completion {
+                text: self.name.to_string(),
+                cursor_offset: 0,
+                confidence: self.confidence,
+                source: CompletionSource::Static,
+                replace_range: None,
+                metadata: None,
+            }])
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn dummy_request() -> CompletionRequest {
+        CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: String::new(),
+            cursor_position: Position { line: 0, column: 0 },
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+        }
+    }
+
+    fn request_with_prefix(current_line: &str) -> CompletionRequest {
+        CompletionRequest {
+            current_line: current_line.to_string(),
+            cursor_position: Position { line: 0, column: current_line.chars().count() },
+            ..dummy_request()
+        }
+    }
+
+    struct FixedProvider {
+        completions: Vec<Completion>,
+    }
+
+    #[async_trait::async_trait]
+    impl CompletionProvider for FixedProvider {
+        async fn complete(&self, _request: &CompletionRequest) -> Result<Vec<Completion>> {
+            Ok(self.completions.clone())
+        }
+
+        fn name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    fn completion(text: &str, confidence: f32) -> Completion {
+        Completion {
+            text: text.to_string(),
+            cursor_offset: 0,
+            confidence,
+            source: CompletionSource::Static,
+            replace_range: None,
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn slow_provider_is_dropped_but_does_not_block_fast_ones() {
+        let mut engine = CompletionEngine::with_config(CompletionEngineConfig {
+            provider_timeout: Duration::from_millis(20),
+            overall_deadline: Duration::from_secs(1),
+            ..CompletionEngineConfig::default()
+        });
+        engine.add_provider(Box::new(DelayedProvider {
+            name: "fast",
+            delay: Duration::from_millis(1),
+            confidence: 0.5,
+        }));
+        engine.add_provider(Box::new(DelayedProvider {
+            name: "slow",
+            delay: Duration::from_millis(200),
+            confidence: 0.9,
+        }));
+
+        let response = engine.get_completions(&dummy_request()).await.unwrap();
+
+        assert_eq!(response.completions.len(), 1);
+        assert_eq!(response.completions[0].text, "fast");
+    }
+
+    #[tokio::test]
+    async fn overall_deadline_bounds_total_wait() {
+        let mut engine = CompletionEngine::with_config(CompletionEngineConfig {
+            provider_timeout: Duration::from_secs(5),
+            overall_deadline: Duration::from_millis(20),
+            ..CompletionEngineConfig::default()
+        });
+        engine.add_provider(Box::new(DelayedProvider {
+            name: "slow",
+            delay: Duration::from_millis(200),
+            confidence: 0.5,
+        }));
+
+        let start = std::time::Instant::now();
+        let response = engine.get_completions(&dummy_request()).await.unwrap();
+
+        assert!(response.completions.is_empty());
+        assert!(start.elapsed() < Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn fuzzy_ranking_drops_non_subsequence_matches() {
+        let mut engine = CompletionEngine::new();
+        engine.add_provider(Box::new(FixedProvider {
+            completions: vec![completion("from_hashmap", 0.5), completion("unrelated_thing", 0.95)],
+        }));
+
+        let response = engine.get_completions(&request_with_prefix("fmh")).await.unwrap();
+
+        assert_eq!(response.completions.len(), 1);
+        assert_eq!(response.completions[0].text, "from_hashmap");
+    }
+
+    #[tokio::test]
+    async fn fuzzy_ranking_prefers_tighter_subsequence_match() {
+        let mut engine = CompletionEngine::new();
+        engine.add_provider(Box::new(FixedProvider {
+            completions: vec![
+                // "fmh" is a subsequence of both, but this one matches with
+                // tighter, less-interrupted runs.
+                completion("from_hashmap", 0.5),
+                completion("file_manager_helper", 0.5),
+            ],
+        }));
+
+        let response = engine.get_completions(&request_with_prefix("fmh")).await.unwrap();
+
+        assert_eq!(response.completions[0].text, "from_hashmap");
+    }
+
+    #[tokio::test]
+    async fn duplicate_texts_merge_keeping_higher_scoring_entry() {
+        let mut engine = CompletionEngine::new();
+        engine.add_provider(Box::new(FixedProvider {
+            completions: vec![completion("from_hashmap", 0.2), completion("from_hashmap", 0.9)],
+        }));
+
+        let response = engine.get_completions(&request_with_prefix("fmh")).await.unwrap();
+
+        assert_eq!(response.completions.len(), 1);
+        assert_eq!(response.completions[0].confidence, 0.9);
+    }
+
+    #[tokio::test]
+    async fn duplicate_texts_merge_even_when_a_distinct_candidate_scores_between_them() {
+        // `from_hashmap`@0.9 and `from_hashmap`@0.1 sandwich a distinct
+        // `file_manager_helper`@0.6 once scored and sorted, so they are no
+        // longer adjacent; dedup must still collapse them to one entry.
+        let mut engine = CompletionEngine::new();
+        engine.add_provider(Box::new(FixedProvider {
+            completions: vec![
+                completion("from_hashmap", 0.1),
+                completion("file_manager_helper", 0.6),
+                completion("from_hashmap", 0.9),
+            ],
+        }));
+
+        let response = engine.get_completions(&request_with_prefix("fmh")).await.unwrap();
+
+        let from_hashmap_count = response.completions.iter().filter(|c| c.text == "from_hashmap").count();
+        assert_eq!(from_hashmap_count, 1);
+        assert_eq!(response.completions.len(), 2);
+        let kept = response.completions.iter().find(|c| c.text == "from_hashmap").unwrap();
+        assert_eq!(kept.confidence, 0.9);
+    }
+
+    #[tokio::test]
+    async fn replace_range_completions_survive_ranking_despite_not_being_a_subsequence() {
+        // "&foo" is not a fuzzy subsequence of the typed prefix "ref", so a
+        // naive fuzzy filter drops it; ranking must special-case
+        // `replace_range` completions instead of discarding them.
+        let mut engine = CompletionEngine::new();
+        engine.add_provider(Box::new(crate::providers::postfix_provider::PostfixPatternProvider::new()));
+
+        let response = engine.get_completions(&request_with_prefix("foo.ref")).await.unwrap();
+
+        assert!(response.completions.iter().any(|c| c.text == "&foo"));
+    }
 }
@@ -3,6 +3,7 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 /// MCP Server configuration compatible with VS Code, MCPHub, Claude Desktop, etc.
@@ -10,6 +11,84 @@ use std::path::{Path, PathBuf};
 pub struct MCPConfig {
     #[serde(rename = "mcpServers")]
     pub mcp_servers: IndexMap<String, ServerConfig>,
+    /// Interactive input definitions referenced by `${input:<id>}` placeholders,
+    /// mirroring VS Code's `inputs` array.
+    #[serde(default)]
+    pub inputs: Vec<InputDefinition>,
+}
+
+/// Definition of a single `${input:<id>}` placeholder, resolved interactively
+/// (or from a supplied override) before the config is parsed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputDefinition {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub input_type: InputType,
+    /// Message shown to the user when prompting for this input.
+    #[serde(default)]
+    pub description: String,
+    /// Choices offered for `pickString` inputs.
+    #[serde(default)]
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InputType {
+    PromptString,
+    Password,
+    PickString,
+}
+
+/// Supplies an answer for a single `${input:<id>}` placeholder.
+pub trait InputResolver {
+    fn resolve(&mut self, input: &InputDefinition) -> Result<String>;
+}
+
+/// Prompts on the terminal: hides keystrokes for `password`, offers a
+/// numbered menu for `pickString`, and reads a plain line otherwise.
+pub struct TerminalInputResolver;
+
+impl InputResolver for TerminalInputResolver {
+    fn resolve(&mut self, input: &InputDefinition) -> Result<String> {
+        match input.input_type {
+            InputType::Password => {
+                print!("{}: ", input.description);
+                io::stdout().flush()?;
+                rpassword::read_password()
+                    .map_err(|e| anyhow!("Failed to read password input '{}': {}", input.id, e))
+            }
+            InputType::PickString => {
+                println!("{}", input.description);
+                let options = input.options.as_deref().unwrap_or(&[]);
+                for (i, option) in options.iter().enumerate() {
+                    println!("  {}) {}", i + 1, option);
+                }
+                print!("Choice: ");
+                io::stdout().flush()?;
+
+                let mut line = String::new();
+                io::stdin().read_line(&mut line)?;
+                let choice: usize = line
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid selection for input '{}'", input.id))?;
+
+                options
+                    .get(choice.wrapping_sub(1))
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Selection out of range for input '{}'", input.id))
+            }
+            InputType::PromptString => {
+                print!("{}: ", input.description);
+                io::stdout().flush()?;
+
+                let mut line = String::new();
+                io::stdin().read_line(&mut line)?;
+                Ok(line.trim().to_string())
+            }
+        }
+    }
 }
 
 /// Individual server configuration
@@ -22,13 +101,64 @@ pub enum ServerConfig {
         args: Option<Vec<String>>,
         env: Option<HashMap<String, String>>,
         disabled: Option<bool>,
+        /// Command to run (via a shell) if `command` isn't found on `PATH`,
+        /// or if the cached installed version doesn't match `version`.
+        install: Option<String>,
+        /// Pinned version, checked against the provisioning cache to
+        /// decide whether `install` needs to run again.
+        version: Option<String>,
     },
     /// Remote server with URL (HTTP/SSE transport)
     Remote {
         url: String,
         headers: Option<HashMap<String, String>>,
+        /// Request timeout in milliseconds; falls back to rmcp's default
+        /// when unset.
+        #[serde(default)]
+        timeout_ms: Option<u64>,
+        /// How to authenticate with this server. Defaults to `none`.
+        #[serde(default)]
+        auth: AuthMethod,
         disabled: Option<bool>,
     },
+    /// Server run on a remote host over SSH, bridged to a local stdio
+    /// transport exactly like `Local`.
+    Ssh {
+        /// SSH destination, e.g. `user@example.com`.
+        host: String,
+        command: String,
+        args: Option<Vec<String>>,
+        env: Option<HashMap<String, String>>,
+        /// Path to a local executable to upload and run on `host` in place
+        /// of a command already installed there. Skipped when a matching
+        /// binary is already cached remotely.
+        remote_binary: Option<String>,
+        disabled: Option<bool>,
+    },
+}
+
+/// Authentication method for a `Remote` server connection. Modeled as
+/// distinct method types behind a shared trait ([`crate::auth::Authenticator`])
+/// rather than one flexible struct, so each method only carries the fields
+/// it needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AuthMethod {
+    /// No authentication; headers are passed through unchanged.
+    None,
+    /// `Authorization: Bearer <token>`. `token` may reference `${env:VAR}`.
+    Bearer { token: String },
+    /// A static secret sent as a custom header. `key` may reference
+    /// `${env:VAR}`.
+    StaticSecret { key: String },
+    /// An arbitrary header. `value` may reference `${env:VAR}`.
+    Header { name: String, value: String },
+}
+
+impl Default for AuthMethod {
+    fn default() -> Self {
+        AuthMethod::None
+    }
 }
 
 impl ServerConfig {
@@ -36,37 +166,97 @@ impl ServerConfig {
         match self {
             ServerConfig::Local { disabled, .. } => disabled.unwrap_or(false),
             ServerConfig::Remote { disabled, .. } => disabled.unwrap_or(false),
+            ServerConfig::Ssh { disabled, .. } => disabled.unwrap_or(false),
         }
     }
 }
 
+/// Source of environment variable lookups, so `${env:...}` expansion and
+/// server overrides can be driven hermetically in tests.
+pub trait Env: Send + Sync {
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+/// `Env` backed by the real process environment.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProcessEnv;
+
+impl Env for ProcessEnv {
+    fn get(&self, key: &str) -> Option<String> {
+        env::var(key).ok()
+    }
+}
+
+/// `Env` backed by an in-memory map, for hermetic tests.
+#[derive(Debug, Default, Clone)]
+pub struct MapEnv(HashMap<String, String>);
+
+impl MapEnv {
+    pub fn new(vars: HashMap<String, String>) -> Self {
+        Self(vars)
+    }
+}
+
+impl Env for MapEnv {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+}
+
 /// Configuration loader with support for multiple config locations
 pub struct ConfigLoader {
     search_paths: Vec<PathBuf>,
+    /// Pre-supplied answers for `${input:<id>}` placeholders, keyed by input
+    /// id. Lets tests and headless runs bypass interactive prompting.
+    input_overrides: HashMap<String, String>,
+    /// Whether unresolved inputs may fall back to an interactive terminal
+    /// prompt. Cleared only by [`ConfigLoader::non_interactive`]; supplying
+    /// overrides via [`ConfigLoader::with_input_overrides`] leaves it set.
+    interactive: bool,
+    /// Source of `${env:...}` lookups and `RUSTY_MCP_*` server overrides.
+    env: Box<dyn Env>,
 }
 
 impl ConfigLoader {
     pub fn new() -> Self {
         Self {
             search_paths: Self::default_search_paths(),
+            input_overrides: HashMap::new(),
+            interactive: true,
+            env: Box::new(ProcessEnv),
         }
     }
 
-    /// Default search paths compatible with existing tools
-    fn default_search_paths() -> Vec<PathBuf> {
-        let mut paths = Vec::new();
+    /// Supply answers for `${input:<id>}` placeholders up front, so tests
+    /// and headless runs never hit a terminal prompt for those ids.
+    pub fn with_input_overrides(mut self, overrides: HashMap<String, String>) -> Self {
+        self.input_overrides = overrides;
+        self
+    }
 
-        // Current directory project configs
-        paths.push(PathBuf::from(".mcphub/servers.json"));
-        paths.push(PathBuf::from(".vscode/mcp.json"));
-        paths.push(PathBuf::from(".cursor/mcp.json"));
+    /// Disable terminal prompting entirely: any input without a supplied
+    /// override fails with a clear error instead of blocking on stdin.
+    pub fn non_interactive(mut self) -> Self {
+        self.interactive = false;
+        self
+    }
 
-        // User home directory configs
-        if let Some(home) = dirs::home_dir() {
-            paths.push(home.join(".config/mcphub/servers.json"));
-            paths.push(home.join(".config/mcp/servers.json"));
-            paths.push(home.join("mcp/servers.json"));
-        }
+    /// Use a custom source for `${env:...}` lookups and `RUSTY_MCP_*`
+    /// overrides, e.g. a [`MapEnv`] in tests.
+    pub fn with_env(mut self, env: Box<dyn Env>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Default search paths compatible with existing tools.
+    ///
+    /// Ordered least-specific to most-specific: user home directory configs
+    /// first, current-directory project configs last, so that
+    /// [`ConfigLoader::load_with_origins`]'s last-wins merge lets a
+    /// project-local server definition fully replace a same-named one from
+    /// the home directory.
+    fn default_search_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
 
         // macOS specific paths
         if cfg!(target_os = "macos") {
@@ -77,6 +267,18 @@ impl ConfigLoader {
             }
         }
 
+        // User home directory configs
+        if let Some(home) = dirs::home_dir() {
+            paths.push(home.join("mcp/servers.json"));
+            paths.push(home.join(".config/mcp/servers.json"));
+            paths.push(home.join(".config/mcphub/servers.json"));
+        }
+
+        // Current directory project configs
+        paths.push(PathBuf::from(".cursor/mcp.json"));
+        paths.push(PathBuf::from(".vscode/mcp.json"));
+        paths.push(PathBuf::from(".mcphub/servers.json"));
+
         paths
     }
 
@@ -85,19 +287,54 @@ impl ConfigLoader {
         self.search_paths.push(path.as_ref().to_path_buf());
     }
 
-    /// Load configuration from the first available file
+    /// Load and merge every existing config file in search order.
+    ///
+    /// Later (more specific) paths override earlier ones on a per-server-name
+    /// basis; unique server names from all layers are unioned. See
+    /// [`ConfigLoader::load_with_origins`] to also learn which file each
+    /// final server came from.
     pub fn load(&self) -> Result<MCPConfig> {
+        self.load_with_origins().map(|(config, _)| config)
+    }
+
+    /// Like [`ConfigLoader::load`], but also returns the path each final
+    /// server entry was read from, so validation warnings and logging can
+    /// point at the originating file.
+    pub fn load_with_origins(&self) -> Result<(MCPConfig, HashMap<String, PathBuf>)> {
+        let mut merged = MCPConfig {
+            mcp_servers: IndexMap::new(),
+            inputs: Vec::new(),
+        };
+        let mut origins = HashMap::new();
+        let mut found_any = false;
+
         for path in &self.search_paths {
-            if path.exists() {
-                tracing::info!("Loading MCP config from: {}", path.display());
-                return self.load_from_file(path);
+            if !path.exists() {
+                continue;
+            }
+            found_any = true;
+
+            tracing::info!("Loading MCP config from: {}", path.display());
+            let config = self.load_from_file(path)?;
+
+            for (name, server) in config.mcp_servers {
+                merged.mcp_servers.insert(name.clone(), server);
+                origins.insert(name, path.clone());
+            }
+
+            for input in config.inputs {
+                match merged.inputs.iter_mut().find(|existing| existing.id == input.id) {
+                    Some(existing) => *existing = input,
+                    None => merged.inputs.push(input),
+                }
             }
         }
 
-        tracing::warn!("No MCP configuration found in search paths");
-        Ok(MCPConfig {
-            mcp_servers: IndexMap::new(),
-        })
+        if !found_any {
+            tracing::warn!("No MCP configuration found in search paths");
+        }
+
+        Ok((merged, origins))
     }
 
     /// Load configuration from a specific file
@@ -105,43 +342,166 @@ impl ConfigLoader {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .map_err(|e| anyhow!("Failed to read config file {}: {}", path.display(), e))?;
+        let preferred_format = ConfigFormat::from_path(path);
+
+        // `inputs` definitions must be known before we can resolve
+        // `${input:<id>}` placeholders elsewhere in the file, so pull just
+        // that array out first; unrelated fields (including unresolved
+        // placeholders) are ignored by serde here.
+        let input_defs: Vec<InputDefinition> =
+            Self::parse_any::<RawInputs>(&content, preferred_format)
+                .map(|raw| raw.inputs)
+                .unwrap_or_default();
+        let input_answers = self.resolve_inputs(&content, &input_defs)?;
 
         // Expand environment variables and special syntax
-        let expanded_content = self.expand_variables(&content)?;
+        let expanded_content = self.expand_variables(&content, &input_answers)?;
+
+        // Try to parse as MCP config first, then Claude Desktop, then VS Code
+        let mut config = Self::parse_any::<MCPConfig>(&expanded_content, preferred_format)
+            .or_else(|| {
+                Self::parse_any::<ClaudeDesktopConfig>(&expanded_content, preferred_format)
+                    .map(Into::into)
+            })
+            .or_else(|| {
+                Self::parse_any::<VSCodeConfig>(&expanded_content, preferred_format)
+                    .map(Into::into)
+            })
+            .ok_or_else(|| anyhow!("Failed to parse config file as any known format"))?;
+
+        self.apply_env_overrides(&mut config);
+
+        Ok(config)
+    }
 
-        // Try to parse as MCP config first
-        if let Ok(config) = serde_json::from_str::<MCPConfig>(&expanded_content) {
-            return Ok(config);
+    /// Try to deserialize `content` as `T`, preferring `preferred` (detected
+    /// from the file extension) but falling back to every other known
+    /// format in turn for extensionless files.
+    fn parse_any<T: serde::de::DeserializeOwned>(
+        content: &str,
+        preferred: Option<ConfigFormat>,
+    ) -> Option<T> {
+        let mut formats = vec![ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml];
+        if let Some(preferred) = preferred {
+            formats.retain(|f| *f != preferred);
+            formats.insert(0, preferred);
         }
 
-        // Try to parse as Claude Desktop config format
-        if let Ok(claude_config) = serde_json::from_str::<ClaudeDesktopConfig>(&expanded_content) {
-            return Ok(claude_config.into());
+        formats.into_iter().find_map(|fmt| fmt.parse(content).ok())
+    }
+
+    /// Apply `RUSTY_MCP_<SERVER>_<FIELD>` overrides from the environment to
+    /// a loaded config, letting operators toggle or redirect servers in
+    /// CI/containers without editing the file. The server name is
+    /// uppercased with dashes replaced by underscores to form the key, e.g.
+    /// `RUSTY_MCP_FILESYSTEM_DISABLED` or `RUSTY_MCP_GITHUB_URL`.
+    fn apply_env_overrides(&self, config: &mut MCPConfig) {
+        for (name, server) in config.mcp_servers.iter_mut() {
+            let prefix = format!("RUSTY_MCP_{}", Self::env_key(name));
+
+            if let Some(value) = self.env.get(&format!("{}_DISABLED", prefix)) {
+                let disabled = matches!(value.to_lowercase().as_str(), "1" | "true" | "yes");
+                match server {
+                    ServerConfig::Local { disabled: d, .. } => *d = Some(disabled),
+                    ServerConfig::Remote { disabled: d, .. } => *d = Some(disabled),
+                    ServerConfig::Ssh { disabled: d, .. } => *d = Some(disabled),
+                }
+            }
+
+            match server {
+                ServerConfig::Local { command, .. } => {
+                    if let Some(value) = self.env.get(&format!("{}_COMMAND", prefix)) {
+                        *command = value;
+                    }
+                }
+                ServerConfig::Remote { url, .. } => {
+                    if let Some(value) = self.env.get(&format!("{}_URL", prefix)) {
+                        *url = value;
+                    }
+                }
+                ServerConfig::Ssh { command, host, .. } => {
+                    if let Some(value) = self.env.get(&format!("{}_COMMAND", prefix)) {
+                        *command = value;
+                    }
+                    if let Some(value) = self.env.get(&format!("{}_HOST", prefix)) {
+                        *host = value;
+                    }
+                }
+            }
         }
+    }
 
-        // Try to parse as VS Code format with "servers" key
-        if let Ok(vscode_config) = serde_json::from_str::<VSCodeConfig>(&expanded_content) {
-            return Ok(vscode_config.into());
+    /// Map a server name to the `RUSTY_MCP_*` key segment that identifies it.
+    fn env_key(name: &str) -> String {
+        name.to_uppercase().replace('-', "_")
+    }
+
+    /// Resolve every `${input:<id>}` placeholder referenced in `content`
+    /// against `defs`, preferring supplied overrides and only prompting the
+    /// terminal (when allowed) for the rest. Each id is resolved once even
+    /// if referenced multiple times.
+    fn resolve_inputs(
+        &self,
+        content: &str,
+        defs: &[InputDefinition],
+    ) -> Result<HashMap<String, String>> {
+        let mut answers = self.input_overrides.clone();
+        let mut resolver = TerminalInputResolver;
+
+        let input_regex = regex::Regex::new(r"\$\{input:([^}]+)\}").unwrap();
+        for cap in input_regex.captures_iter(content) {
+            let id = &cap[1];
+            if answers.contains_key(id) {
+                continue;
+            }
+
+            let def = defs
+                .iter()
+                .find(|d| d.id == id)
+                .ok_or_else(|| anyhow!("No input definition found for '${{input:{}}}'", id))?;
+
+            if !self.interactive {
+                return Err(anyhow!(
+                    "Input '{}' has no supplied override and interactive prompting is disabled",
+                    id
+                ));
+            }
+
+            let answer = resolver.resolve(def)?;
+            answers.insert(id.to_string(), answer);
         }
 
-        Err(anyhow!("Failed to parse config file as any known format"))
+        Ok(answers)
     }
 
-    /// Expand environment variables and special syntax like ${env:VAR}, ${input:prompt}
-    fn expand_variables(&self, content: &str) -> Result<String> {
+    /// Expand environment variables and special syntax like ${env:VAR}, ${input:id}
+    fn expand_variables(
+        &self,
+        content: &str,
+        input_answers: &HashMap<String, String>,
+    ) -> Result<String> {
         let mut result = content.to_string();
 
         // Expand ${env:VARIABLE_NAME} syntax
         let env_regex = regex::Regex::new(r"\$\{env:([^}]+)\}").unwrap();
         for cap in env_regex.captures_iter(content) {
             let var_name = &cap[1];
-            let replacement = env::var(var_name).unwrap_or_else(|_| {
+            let replacement = self.env.get(var_name).unwrap_or_else(|| {
                 tracing::warn!("Environment variable {} not found", var_name);
                 String::new()
             });
             result = result.replace(&cap[0], &replacement);
         }
 
+        // Expand ${input:<id>} syntax using the answers resolved up front
+        let input_regex = regex::Regex::new(r"\$\{input:([^}]+)\}").unwrap();
+        for cap in input_regex.captures_iter(content) {
+            let id = &cap[1];
+            if let Some(answer) = input_answers.get(id) {
+                result = result.replace(&cap[0], answer);
+            }
+        }
+
         // Expand home directory (~)
         result = shellexpand::tilde(&result).to_string();
 
@@ -149,6 +509,53 @@ impl ConfigLoader {
     }
 }
 
+/// Helper for pulling just the `inputs` array out of a config file before
+/// the rest of its contents (which may still contain unresolved
+/// placeholders) are parsed.
+#[derive(Debug, Default, Deserialize)]
+struct RawInputs {
+    #[serde(default)]
+    inputs: Vec<InputDefinition>,
+}
+
+/// Serialization format of a config file, detected by extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Detect the format from a file's extension, if recognized.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Some(Self::Json),
+            Some("yaml") | Some("yml") => Some(Self::Yaml),
+            Some("toml") => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    fn parse<T: serde::de::DeserializeOwned>(self, content: &str) -> Result<T> {
+        match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| anyhow!(e)),
+            Self::Yaml => serde_yaml::from_str(content).map_err(|e| anyhow!(e)),
+            Self::Toml => toml::from_str(content).map_err(|e| anyhow!(e)),
+        }
+    }
+
+    /// Serialize `value` in this format, e.g. when writing a sample config
+    /// back out in the same format it was read.
+    pub fn serialize<T: Serialize>(self, value: &T) -> Result<String> {
+        match self {
+            Self::Json => Ok(serde_json::to_string_pretty(value)?),
+            Self::Yaml => Ok(serde_yaml::to_string(value)?),
+            Self::Toml => Ok(toml::to_string_pretty(value)?),
+        }
+    }
+}
+
 impl Default for ConfigLoader {
     fn default() -> Self {
         Self::new()
@@ -166,6 +573,7 @@ impl From<ClaudeDesktopConfig> for MCPConfig {
     fn from(config: ClaudeDesktopConfig) -> Self {
         MCPConfig {
             mcp_servers: config.mcp_servers,
+            inputs: Vec::new(),
         }
     }
 }
@@ -174,12 +582,15 @@ impl From<ClaudeDesktopConfig> for MCPConfig {
 #[derive(Debug, Deserialize)]
 struct VSCodeConfig {
     servers: IndexMap<String, ServerConfig>,
+    #[serde(default)]
+    inputs: Vec<InputDefinition>,
 }
 
 impl From<VSCodeConfig> for MCPConfig {
     fn from(config: VSCodeConfig) -> Self {
         MCPConfig {
             mcp_servers: config.servers,
+            inputs: config.inputs,
         }
     }
 }
@@ -188,30 +599,68 @@ impl From<VSCodeConfig> for MCPConfig {
 impl MCPConfig {
     /// Validate the configuration
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_origins(&HashMap::new())
+    }
+
+    /// Validate the configuration, attributing each warning/error to the
+    /// file it came from when `origins` (as returned by
+    /// [`ConfigLoader::load_with_origins`]) has an entry for that server.
+    pub fn validate_with_origins(&self, origins: &HashMap<String, PathBuf>) -> Result<()> {
         for (name, server) in &self.mcp_servers {
+            let origin = origins
+                .get(name)
+                .map(|path| format!(" (from {})", path.display()))
+                .unwrap_or_default();
+
             match server {
                 ServerConfig::Local { command, .. } => {
                     if command.is_empty() {
-                        return Err(anyhow!("Server '{}' has empty command", name));
+                        return Err(anyhow!("Server '{}'{} has empty command", name, origin));
                     }
 
                     // Check if command exists in PATH
                     if which::which(command).is_err() {
                         tracing::warn!(
-                            "Command '{}' for server '{}' not found in PATH",
+                            "Command '{}' for server '{}'{} not found in PATH",
                             command,
-                            name
+                            name,
+                            origin
                         );
                     }
                 }
                 ServerConfig::Remote { url, .. } => {
                     if url.is_empty() {
-                        return Err(anyhow!("Server '{}' has empty URL", name));
+                        return Err(anyhow!("Server '{}'{} has empty URL", name, origin));
                     }
 
                     // Basic URL validation
                     if !url.starts_with("http://") && !url.starts_with("https://") {
-                        return Err(anyhow!("Server '{}' has invalid URL: {}", name, url));
+                        return Err(anyhow!(
+                            "Server '{}'{} has invalid URL: {}",
+                            name,
+                            origin,
+                            url
+                        ));
+                    }
+                }
+                ServerConfig::Ssh {
+                    host,
+                    remote_binary,
+                    ..
+                } => {
+                    if host.is_empty() {
+                        return Err(anyhow!("Server '{}'{} has empty host", name, origin));
+                    }
+
+                    if let Some(binary) = remote_binary {
+                        if !Path::new(binary).exists() {
+                            return Err(anyhow!(
+                                "Server '{}'{} has remote_binary '{}' which does not exist locally",
+                                name,
+                                origin,
+                                binary
+                            ));
+                        }
                     }
                 }
             }
@@ -241,6 +690,8 @@ impl MCPConfig {
                 ]),
                 env: None,
                 disabled: Some(false),
+                install: None,
+                version: None,
             },
         );
 
@@ -251,6 +702,8 @@ impl MCPConfig {
                 args: Some(vec!["mcp-server-fetch".to_string()]),
                 env: None,
                 disabled: Some(false),
+                install: Some("pip install --user mcp-server-fetch".to_string()),
+                version: Some("0.1.0".to_string()),
             },
         );
 
@@ -258,20 +711,80 @@ impl MCPConfig {
             "github".to_string(),
             ServerConfig::Remote {
                 url: "https://api.githubcopilot.com/mcp/".to_string(),
-                headers: Some({
-                    let mut headers = HashMap::new();
-                    headers.insert(
-                        "Authorization".to_string(),
-                        "Bearer ${env:GITHUB_PERSONAL_ACCESS_TOKEN}".to_string(),
-                    );
-                    headers
-                }),
+                headers: None,
+                timeout_ms: None,
+                auth: AuthMethod::Bearer {
+                    token: "${env:GITHUB_PERSONAL_ACCESS_TOKEN}".to_string(),
+                },
+                disabled: Some(true),
+            },
+        );
+
+        servers.insert(
+            "remote-build-box".to_string(),
+            ServerConfig::Ssh {
+                host: "build@ci.internal".to_string(),
+                command: "mcp-server-filesystem".to_string(),
+                args: Some(vec!["/srv/repo".to_string()]),
+                env: None,
+                remote_binary: None,
                 disabled: Some(true),
             },
         );
 
         MCPConfig {
             mcp_servers: servers,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Unique scratch directory per test, so parallel test runs don't
+    /// trample each other's config files.
+    fn scratch_dir(name: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!(
+            "rusty-mcp-config-test-{}-{}-{}",
+            std::process::id(),
+            n,
+            name
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn project_layer_fully_replaces_same_named_home_layer_server() {
+        let dir = scratch_dir("precedence");
+        let home_config = dir.join("home.json");
+        let project_config = dir.join("project.json");
+
+        std::fs::write(
+            &home_config,
+            r#"{"mcpServers":{"filesystem":{"command":"home-fs","args":["/home"]}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &project_config,
+            r#"{"mcpServers":{"filesystem":{"command":"project-fs","args":["/project"]}}}"#,
+        )
+        .unwrap();
+
+        let mut loader = ConfigLoader::new();
+        loader.search_paths = vec![home_config.clone(), project_config.clone()];
+
+        let (config, origins) = loader.load_with_origins().unwrap();
+
+        match &config.mcp_servers["filesystem"] {
+            ServerConfig::Local { command, .. } => assert_eq!(command, "project-fs"),
+            other => panic!("expected a Local server, got {:?}", other),
         }
+        assert_eq!(origins["filesystem"], project_config);
     }
 }
@@ -0,0 +1,137 @@
+//! Fuzzy subsequence matching used to rank completions against what the
+//! user has actually typed, rather than relying on provider-reported
+//! `confidence` alone.
+//!
+//! [`score`] implements an editor-style subsequence matcher: query
+//! characters must appear in the candidate in order (case-insensitively),
+//! earning a base point per matched character plus bonuses for consecutive
+//! runs and for matches at word boundaries (after `_`, `.`, `::`, or a
+//! camelCase hump), and a penalty proportional to skipped characters. A
+//! candidate that doesn't contain the query as a subsequence at all is
+//! rejected outright.
+
+/// Bonus added when a matched character immediately follows the previous
+/// matched character (a consecutive run).
+const CONSECUTIVE_BONUS: f32 = 1.0;
+/// Bonus added when a matched character starts a "word" (the first
+/// character, or right after `_`/`.`/`:`, or a camelCase hump).
+const BOUNDARY_BONUS: f32 = 1.0;
+/// Cost per unmatched character skipped over before a match, whether
+/// leading into the candidate or between two matched characters.
+const SKIP_PENALTY: f32 = 0.2;
+
+/// Score `candidate` against `query` as a fuzzy subsequence match,
+/// normalized to `0.0..=1.0`. Returns `None` if `query` is not a
+/// subsequence of `candidate`, i.e. typing `query` could never complete to
+/// `candidate`. An empty `query` always scores `1.0`, since every
+/// candidate is an equally good completion when the user hasn't typed
+/// anything yet.
+pub fn score(query: &str, candidate: &str) -> Option<f32> {
+    if query.is_empty() {
+        return Some(1.0);
+    }
+
+    let query: Vec<char> = query.chars().collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+
+    let mut query_idx = 0;
+    let mut last_match: Option<usize> = None;
+    let mut raw = 0.0f32;
+
+    for (i, &c) in candidate.iter().enumerate() {
+        if query_idx >= query.len() {
+            break;
+        }
+        if !c.eq_ignore_ascii_case(&query[query_idx]) {
+            continue;
+        }
+
+        raw += 1.0;
+
+        let skipped = match last_match {
+            Some(last) => i - last - 1,
+            None => i,
+        };
+        if skipped == 0 && last_match.is_some() {
+            raw += CONSECUTIVE_BONUS;
+        } else {
+            raw -= skipped as f32 * SKIP_PENALTY;
+        }
+
+        let at_boundary = i == 0
+            || matches!(candidate[i - 1], '_' | '.' | ':')
+            || (candidate[i - 1].is_lowercase() && c.is_uppercase());
+        if at_boundary {
+            raw += BOUNDARY_BONUS;
+        }
+
+        last_match = Some(i);
+        query_idx += 1;
+    }
+
+    if query_idx < query.len() {
+        return None;
+    }
+
+    let max_possible = query.len() as f32 * (1.0 + CONSECUTIVE_BONUS.max(BOUNDARY_BONUS));
+    Some((raw.max(0.0) / max_possible).clamp(0.0, 1.0))
+}
+
+/// Extract the identifier prefix immediately before the cursor from
+/// `current_line`, e.g. `"let x = fm"` at column 10 yields `"fm"`.
+pub fn prefix_at(current_line: &str, column: usize) -> String {
+    let chars: Vec<char> = current_line.chars().collect();
+    let end = column.min(chars.len());
+
+    let mut start = end;
+    while start > 0 && (chars[start - 1].is_alphanumeric() || chars[start - 1] == '_') {
+        start -= 1;
+    }
+
+    chars[start..end].iter().collect()
+}
+
+/// Blend a fuzzy match score with a provider's reported confidence:
+/// `weight * fuzzy + (1 - weight) * confidence`.
+pub fn blended_score(fuzzy: f32, confidence: f32, weight: f32) -> f32 {
+    weight * fuzzy + (1.0 - weight) * confidence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_non_subsequence() {
+        assert_eq!(score("xyz", "from_hashmap"), None);
+    }
+
+    #[test]
+    fn empty_query_scores_max() {
+        assert_eq!(score("", "anything"), Some(1.0));
+    }
+
+    #[test]
+    fn consecutive_prefix_beats_scattered_match() {
+        let prefix = score("fro", "from_hashmap").unwrap();
+        let scattered = score("fhm", "from_hashmap").unwrap();
+        assert!(prefix > scattered);
+    }
+
+    #[test]
+    fn word_boundary_match_beats_mid_word_match() {
+        // "fhm" matches the first character and the one after `_`; "ram"
+        // skips as many characters and has no consecutive run, but only
+        // matches mid-word.
+        let boundary = score("fhm", "from_hashmap").unwrap();
+        let mid_word = score("ram", "from_hashmap").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn prefix_at_extracts_identifier_before_cursor() {
+        assert_eq!(prefix_at("let x = fm", 10), "fm");
+        assert_eq!(prefix_at("self.foo_bar", 12), "foo_bar".to_string());
+        assert_eq!(prefix_at("", 0), "");
+    }
+}
@@ -42,21 +42,32 @@
 //! }
 //! ```
 
+pub mod agent;
+pub mod auth;
 pub mod client;
 pub mod completion;
 pub mod config;
+pub mod fuzzy;
 pub mod providers;
+pub mod provisioning;
 pub mod server;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+pub use agent::{
+    AgentLlm, AgentLoop, AgentMessage, AgentStep, AgentTool, OpenAiAgentLlm, ToolCallRequest,
+};
 pub use client::{
-    ConnectionStatus, MCPClient, MCPServerConnection, MCPService, Resource, ResourceContent, Tool,
-    ToolResult, ToolResultContent,
+    ConnectionStatus, MCPClient, MCPServerConnection, MCPService, Resource, ResourceContent,
+    SupervisorConfig, Tool, ToolConflictPolicy, ToolResult, ToolResultContent,
 };
 pub use completion::{
-    Completion, CompletionEngine, CompletionProvider, CompletionRequest, CompletionResponse,
-    CompletionSource, Pattern, PatternDetector, Position,
+    Completion, CompletionEngine, CompletionEngineConfig, CompletionProvider, CompletionRequest,
+    CompletionResponse, CompletionSource, Pattern, PatternDetector, Position,
 };
-pub use config::{ConfigLoader, MCPConfig, ServerConfig};
+pub use auth::Authenticator;
+pub use config::{AuthMethod, ConfigLoader, Env, MCPConfig, MapEnv, ProcessEnv, ServerConfig};
+pub use provisioning::{ProvisionCache, Provisioner};
 pub use server::JsonRpcServer;
 
 // Re-export commonly used types
@@ -1,11 +1,23 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Arg, Command};
-use std::io::{self, Write};
+use reedline::{
+    Completer, FileBackedHistory, Prompt, PromptEditMode, PromptHistorySearch,
+    PromptHistorySearchStatus, Reedline, Signal, Span, Suggestion,
+};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use tracing::{info, Level};
 use tracing_subscriber;
 
+use std::path::{Path, PathBuf};
+
 // Import from our library crate
-use nvim_mcp_client::{ConfigLoader, ConnectionStatus, MCPClient, MCPConfig};
+use nvim_mcp_client::config::ConfigFormat;
+use nvim_mcp_client::{AgentLoop, ConfigLoader, ConnectionStatus, MCPClient, MCPConfig, OpenAiAgentLlm};
+
+/// Default cap on agent reasoning steps when not overridden by `--max-steps`.
+const DEFAULT_AGENT_MAX_STEPS: usize = 10;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -90,6 +102,22 @@ async fn main() -> Result<()> {
         )
         .subcommand(Command::new("status").about("Show connection status for all servers"))
         .subcommand(Command::new("interactive").about("Start interactive mode"))
+        .subcommand(
+            Command::new("agent")
+                .about("Run an autonomous tool-calling agent loop over the connected MCP servers")
+                .arg(
+                    Arg::new("prompt")
+                        .help("Task to give the agent")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("max-steps")
+                        .long("max-steps")
+                        .value_name("N")
+                        .help("Maximum reasoning steps before aborting")
+                        .default_value("10"),
+                ),
+        )
         .get_matches();
 
     // Initialize logging
@@ -154,6 +182,15 @@ async fn main() -> Result<()> {
         Some(("interactive", _)) => {
             interactive_mode(client).await?;
         }
+        Some(("agent", sub_matches)) => {
+            let prompt = sub_matches.get_one::<String>("prompt").unwrap();
+            let max_steps = sub_matches
+                .get_one::<String>("max-steps")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_AGENT_MAX_STEPS);
+
+            run_agent(&client, prompt, max_steps).await;
+        }
         None => {
             // Default: show status and enter interactive mode
             show_status(&client).await?;
@@ -315,7 +352,7 @@ async fn read_resource(client: &MCPClient, server: &str, uri: &str) -> Result<()
 }
 
 async fn show_status(client: &MCPClient) -> Result<()> {
-    let status = client.get_server_status();
+    let status = client.get_server_status().await;
 
     println!("Server Connection Status:");
     println!("========================");
@@ -324,6 +361,7 @@ async fn show_status(client: &MCPClient) -> Result<()> {
         let status_icon = match status {
             ConnectionStatus::Connected => "‚úÖ",
             ConnectionStatus::Connecting => "üîÑ",
+            ConnectionStatus::Reconnecting { .. } => "🔁",
             ConnectionStatus::Disconnected => "‚≠ï",
             ConnectionStatus::Failed(_) => "‚ùå",
         };
@@ -334,16 +372,215 @@ async fn show_status(client: &MCPClient) -> Result<()> {
     Ok(())
 }
 
+/// Run the agent loop for `prompt` against `client`'s connected tools and
+/// print the outcome. Reports (rather than propagates) failures so a single
+/// bad agent run doesn't tear down the caller's `interactive_mode` session.
+async fn run_agent(client: &MCPClient, prompt: &str, max_steps: usize) {
+    let llm = match OpenAiAgentLlm::from_env() {
+        Ok(llm) => llm,
+        Err(e) => {
+            println!("‚ùå Could not start agent: {}", e);
+            return;
+        }
+    };
+
+    let agent = AgentLoop::new(client, &llm, max_steps);
+
+    println!("Running agent (max {} steps): {}", max_steps, prompt);
+
+    match agent.run(prompt).await {
+        Ok(answer) => println!("‚úÖ {}", answer),
+        Err(e) => println!("‚ùå Agent failed: {}", e),
+    }
+}
+
+/// Top-level commands offered when completing the first token of a line.
+const COMMANDS: &[&str] = &[
+    "help",
+    "status",
+    "list-tools",
+    "list-resources",
+    "call",
+    "read",
+    "reconnect",
+    "agent",
+    "quit",
+    "exit",
+];
+
+/// Commands whose first argument is a server name.
+const SERVER_ARG_COMMANDS: &[&str] = &["call", "read", "reconnect"];
+
+/// Snapshot of server/tool/resource names used to drive tab completion,
+/// refreshed from `client` once per prompt so completions stay current
+/// without making the completer itself async.
+#[derive(Debug, Clone, Default)]
+struct InteractiveSnapshot {
+    servers: Vec<String>,
+    tools: HashMap<String, Vec<String>>,
+    resources: HashMap<String, Vec<String>>,
+}
+
+impl InteractiveSnapshot {
+    async fn fetch(client: &MCPClient) -> Self {
+        let servers = client.get_server_status().await.into_keys().collect();
+
+        let tools = client
+            .list_all_tools()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(server, tools)| (server, tools.into_iter().map(|t| t.name).collect()))
+            .collect();
+
+        let resources = client
+            .list_all_resources()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(server, resources)| (server, resources.into_iter().map(|r| r.uri).collect()))
+            .collect();
+
+        Self {
+            servers,
+            tools,
+            resources,
+        }
+    }
+}
+
+/// Context-aware completer for `interactive_mode`: the first token
+/// completes to the command set, the argument after `call`/`read`/
+/// `reconnect` completes to a live server name, and the token after that
+/// completes to the chosen server's tool names (`call`) or resource URIs
+/// (`read`).
+struct McpCommandCompleter {
+    snapshot: Arc<RwLock<InteractiveSnapshot>>,
+}
+
+impl Completer for McpCommandCompleter {
+    fn complete(&mut self, line: &str, pos: usize) -> Vec<Suggestion> {
+        let before_cursor = &line[..pos];
+        let token_start = before_cursor
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let current = &before_cursor[token_start..];
+        let completed: Vec<&str> = before_cursor[..token_start].split_whitespace().collect();
+        let span = Span::new(token_start, pos);
+
+        let snapshot = self.snapshot.read().unwrap();
+        let candidates: Vec<String> = match completed.len() {
+            0 => COMMANDS.iter().map(|s| s.to_string()).collect(),
+            1 if SERVER_ARG_COMMANDS.contains(&completed[0]) => snapshot.servers.clone(),
+            2 if completed[0] == "call" => snapshot
+                .tools
+                .get(completed[1])
+                .cloned()
+                .unwrap_or_default(),
+            2 if completed[0] == "read" => snapshot
+                .resources
+                .get(completed[1])
+                .cloned()
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(current))
+            .map(|value| Suggestion {
+                value,
+                description: None,
+                style: None,
+                extra: None,
+                span,
+                append_whitespace: true,
+            })
+            .collect()
+    }
+}
+
+/// Where command history is persisted across sessions.
+fn history_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|home| home.join(".config/mcp/history.txt"))
+        .unwrap_or_else(|| PathBuf::from(".mcp_history.txt"))
+}
+
+struct McpPrompt;
+
+impl Prompt for McpPrompt {
+    fn render_prompt_left(&self) -> Cow<str> {
+        Cow::Borrowed("mcp")
+    }
+
+    fn render_prompt_right(&self) -> Cow<str> {
+        Cow::Borrowed("")
+    }
+
+    fn render_prompt_indicator(&self, _edit_mode: PromptEditMode) -> Cow<str> {
+        Cow::Borrowed("> ")
+    }
+
+    fn render_prompt_multiline_indicator(&self) -> Cow<str> {
+        Cow::Borrowed("::: ")
+    }
+
+    fn render_prompt_history_search_indicator(
+        &self,
+        history_search: PromptHistorySearch,
+    ) -> Cow<str> {
+        let prefix = if history_search.status == PromptHistorySearchStatus::Failing {
+            "failing "
+        } else {
+            ""
+        };
+        Cow::Owned(format!(
+            "({}reverse-search: {}) ",
+            prefix, history_search.term
+        ))
+    }
+}
+
 async fn interactive_mode(mut client: MCPClient) -> Result<()> {
     println!("Interactive MCP Client");
     println!("Type 'help' for available commands, 'quit' to exit");
 
+    let history_path = history_path();
+    if let Some(parent) = history_path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    let history = Box::new(
+        FileBackedHistory::with_file(1000, history_path)
+            .context("failed to open command history file")?,
+    );
+
+    let snapshot = Arc::new(RwLock::new(InteractiveSnapshot::default()));
+    let completer = Box::new(McpCommandCompleter {
+        snapshot: snapshot.clone(),
+    });
+
+    let mut editor = Reedline::create()
+        .with_history(history)
+        .with_completer(completer);
+    let prompt = McpPrompt;
+
     loop {
-        print!("mcp> ");
-        io::stdout().flush()?;
+        *snapshot.write().unwrap() = InteractiveSnapshot::fetch(&client).await;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = match editor.read_line(&prompt) {
+            Ok(Signal::Success(line)) => line,
+            Ok(Signal::CtrlC) | Ok(Signal::CtrlD) => {
+                println!("Shutting down...");
+                client.shutdown().await?;
+                break;
+            }
+            Err(e) => {
+                println!("‚ùå Input error: {}", e);
+                continue;
+            }
+        };
         let input = input.trim();
 
         if input.is_empty() {
@@ -362,6 +599,7 @@ async fn interactive_mode(mut client: MCPClient) -> Result<()> {
                 println!("  call <server> <tool> [args] - Call a tool");
                 println!("  read <server> <uri>      - Read a resource");
                 println!("  reconnect <server>       - Reconnect to a server");
+                println!("  agent <prompt>           - Run an autonomous tool-calling agent");
                 println!("  quit                     - Exit");
             }
             "status" => {
@@ -413,6 +651,15 @@ async fn interactive_mode(mut client: MCPClient) -> Result<()> {
                     Err(e) => println!("‚ùå Failed to reconnect to server {}: {}", server, e),
                 }
             }
+            "agent" => {
+                if parts.len() < 2 {
+                    println!("Usage: agent <prompt>");
+                    continue;
+                }
+
+                let prompt = parts[1..].join(" ");
+                run_agent(&client, &prompt, DEFAULT_AGENT_MAX_STEPS).await;
+            }
             "quit" | "exit" => {
                 println!("Shutting down...");
                 client.shutdown().await?;
@@ -432,9 +679,10 @@ async fn interactive_mode(mut client: MCPClient) -> Result<()> {
 
 fn create_sample_config(path: &str) -> Result<()> {
     let config = MCPConfig::create_sample_config();
-    let json = serde_json::to_string_pretty(&config)?;
+    let format = ConfigFormat::from_path(Path::new(path)).unwrap_or(ConfigFormat::Json);
+    let serialized = format.serialize(&config)?;
 
-    std::fs::write(path, json)?;
+    std::fs::write(path, serialized)?;
 
     println!("‚úÖ Sample configuration created at: {}", path);
     println!("Edit the file to configure your MCP servers.");
@@ -0,0 +1,432 @@
+//! Cargo/clippy-backed diagnostics provider.
+//!
+//! Runs `cargo check --message-format=json` (and optionally `cargo clippy`)
+//! for the workspace containing a Rust file, turning compiler diagnostics
+//! whose span covers the cursor into actionable completions sourced from
+//! machine-applicable `suggested_replacement` spans. Results are cached per
+//! file keyed by `(path, mtime)`: `complete()` never runs the check inline,
+//! it serves whatever is cached and kicks off a background refresh when the
+//! file has changed, so a check never blocks a keystroke.
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::process::Command;
+use tokio::sync::RwLock;
+
+use crate::client::{Resource, ResourceContent};
+use crate::completion::{
+    Completion, CompletionProvider, CompletionRequest, CompletionSource, Pattern, PatternDetector,
+};
+
+/// A diagnostic trimmed down to the fields this provider acts on, owned so
+/// it can be cached independently of the `cargo` JSON it was parsed from.
+#[derive(Debug, Clone)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    code: Option<String>,
+    /// 0-indexed, inclusive line range the diagnostic's primary span covers.
+    line_start: usize,
+    line_end: usize,
+    suggested_replacement: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CargoMessageLine {
+    reason: String,
+    #[serde(default)]
+    message: Option<RawCompilerMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCompilerMessage {
+    message: String,
+    level: String,
+    spans: Vec<RawSpan>,
+    #[serde(default)]
+    code: Option<RawCode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSpan {
+    file_name: String,
+    line_start: usize,
+    line_end: usize,
+    is_primary: bool,
+    #[serde(default)]
+    suggested_replacement: Option<String>,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    mtime: SystemTime,
+    diagnostics: Arc<Vec<Diagnostic>>,
+}
+
+/// `CompletionProvider` that surfaces `cargo check`/clippy diagnostics
+/// touching the cursor's line as completions, plus a dedicated "fill match
+/// arms" completion when the cursor sits in a non-exhaustive `match`.
+pub struct DiagnosticsProvider {
+    cache: Arc<RwLock<HashMap<PathBuf, CacheEntry>>>,
+    run_clippy: bool,
+    enabled: bool,
+}
+
+impl DiagnosticsProvider {
+    pub fn new() -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            run_clippy: false,
+            enabled: true,
+        }
+    }
+
+    /// Also run `cargo clippy` alongside `cargo check` on each refresh.
+    pub fn with_clippy(mut self, run_clippy: bool) -> Self {
+        self.run_clippy = run_clippy;
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// The raw diagnostics currently cached for `file_path`, exposed
+    /// MCP-resource-style for callers that want the full set rather than
+    /// just the completions derived from it.
+    pub async fn resource(&self, file_path: &str) -> Option<Resource> {
+        let path = PathBuf::from(file_path);
+        let cache = self.cache.read().await;
+        cache.get(&path).map(|_| Resource {
+            uri: format!("diagnostics://{}", file_path),
+            name: format!("Diagnostics for {}", file_path),
+            description: Some("cargo check/clippy diagnostics".to_string()),
+            mime_type: Some("application/json".to_string()),
+        })
+    }
+
+    /// The raw diagnostics for `file_path`, serialized as the resource's content.
+    pub async fn resource_content(&self, file_path: &str) -> Option<ResourceContent> {
+        let path = PathBuf::from(file_path);
+        let cache = self.cache.read().await;
+        let entry = cache.get(&path)?;
+        let json = serde_json::to_string(
+            &entry
+                .diagnostics
+                .iter()
+                .map(|d| {
+                    serde_json::json!({
+                        "message": d.message,
+                        "level": d.level,
+                        "code": d.code,
+                        "line_start": d.line_start,
+                        "line_end": d.line_end,
+                        "suggested_replacement": d.suggested_replacement,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+        .ok()?;
+
+        Some(ResourceContent {
+            uri: format!("diagnostics://{}", file_path),
+            mime_type: Some("application/json".to_string()),
+            text: Some(json),
+            blob: None,
+        })
+    }
+
+    /// Kick off a background `cargo check` (and optional clippy) run for
+    /// `file_path`, updating the cache when it completes. Errors are
+    /// logged, not propagated, since this runs detached from any request.
+    fn spawn_refresh(&self, file_path: PathBuf, mtime: SystemTime) {
+        let cache = Arc::clone(&self.cache);
+        let run_clippy = self.run_clippy;
+
+        tokio::spawn(async move {
+            match run_cargo_diagnostics(&file_path, run_clippy).await {
+                Ok(diagnostics) => {
+                    cache.write().await.insert(
+                        file_path,
+                        CacheEntry {
+                            mtime,
+                            diagnostics: Arc::new(diagnostics),
+                        },
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "cargo diagnostics refresh for {} failed: {}",
+                        file_path.display(),
+                        e
+                    );
+                }
+            }
+        });
+    }
+
+    /// Turn cached diagnostics whose span covers `request`'s cursor line
+    /// into completions.
+    fn diagnostics_to_completions(
+        &self,
+        request: &CompletionRequest,
+        pattern: Pattern,
+        diagnostics: &[Diagnostic],
+    ) -> Vec<Completion> {
+        let cursor_line = request.cursor_position.line;
+        let mut completions = Vec::new();
+
+        for diagnostic in diagnostics {
+            if cursor_line < diagnostic.line_start || cursor_line > diagnostic.line_end {
+                continue;
+            }
+
+            if pattern == Pattern::MatchStatement && diagnostic.code.as_deref() == Some("E0004") {
+                if let Some(stub) = fill_match_arms(&diagnostic.message) {
+                    completions.push(Completion {
+                        text: stub,
+                        cursor_offset: 0,
+                        confidence: 0.85,
+                        source: CompletionSource::Diagnostic,
+                        replace_range: None,
+                        metadata: Some(serde_json::json!({
+                            "code": diagnostic.code,
+                            "kind": "fill_match_arms",
+                        })),
+                    });
+                    continue;
+                }
+            }
+
+            if let Some(replacement) = &diagnostic.suggested_replacement {
+                completions.push(Completion {
+                    text: replacement.clone(),
+                    cursor_offset: 0,
+                    confidence: 0.7,
+                    source: CompletionSource::Diagnostic,
+                    replace_range: None,
+                    metadata: Some(serde_json::json!({
+                        "code": diagnostic.code,
+                        "message": diagnostic.message,
+                        "level": diagnostic.level,
+                    })),
+                });
+            }
+        }
+
+        completions
+    }
+}
+
+impl Default for DiagnosticsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionProvider for DiagnosticsProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        if request.language != "rust" {
+            return Ok(Vec::new());
+        }
+
+        let file_path = PathBuf::from(&request.file_path);
+        let mtime = match file_mtime(&file_path) {
+            Ok(mtime) => mtime,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let cached = {
+            let cache = self.cache.read().await;
+            cache
+                .get(&file_path)
+                .filter(|entry| entry.mtime == mtime)
+                .cloned()
+        };
+
+        let diagnostics = match cached {
+            Some(entry) => entry.diagnostics,
+            None => {
+                self.spawn_refresh(file_path, mtime);
+                return Ok(Vec::new());
+            }
+        };
+
+        let pattern = super::static_provider::StaticPatternDetector
+            .detect_pattern(&request.current_line, &request.language);
+
+        Ok(self.diagnostics_to_completions(request, pattern, &diagnostics))
+    }
+
+    fn name(&self) -> &str {
+        "cargo-diagnostics"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+fn file_mtime(path: &Path) -> Result<SystemTime> {
+    std::fs::metadata(path)
+        .with_context(|| format!("failed to stat {}", path.display()))?
+        .modified()
+        .with_context(|| format!("no mtime available for {}", path.display()))
+}
+
+/// Walk up from `file_path` looking for the nearest `Cargo.toml`.
+fn find_workspace_root(file_path: &Path) -> Option<PathBuf> {
+    let mut dir = file_path.parent()?;
+    loop {
+        if dir.join("Cargo.toml").exists() {
+            return Some(dir.to_path_buf());
+        }
+        dir = dir.parent()?;
+    }
+}
+
+/// Run `cargo check` (and optionally `cargo clippy`) in the workspace
+/// containing `file_path`, parsing the streamed `--message-format=json`
+/// compiler messages into `Diagnostic`s scoped to `file_path`.
+async fn run_cargo_diagnostics(file_path: &Path, run_clippy: bool) -> Result<Vec<Diagnostic>> {
+    let workspace_root = find_workspace_root(file_path)
+        .ok_or_else(|| anyhow!("no Cargo.toml found above {}", file_path.display()))?;
+
+    let mut diagnostics = run_cargo_subcommand(&workspace_root, "check", file_path).await?;
+    if run_clippy {
+        diagnostics.extend(run_cargo_subcommand(&workspace_root, "clippy", file_path).await?);
+    }
+    Ok(diagnostics)
+}
+
+async fn run_cargo_subcommand(
+    workspace_root: &Path,
+    subcommand: &str,
+    file_path: &Path,
+) -> Result<Vec<Diagnostic>> {
+    let output = Command::new("cargo")
+        .arg(subcommand)
+        .arg("--message-format=json")
+        .current_dir(workspace_root)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .with_context(|| format!("failed to run `cargo {}`", subcommand))?;
+
+    let file_name = file_path.to_string_lossy().to_string();
+    let mut diagnostics = Vec::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(parsed) = serde_json::from_str::<CargoMessageLine>(line) else {
+            continue;
+        };
+        if parsed.reason != "compiler-message" {
+            continue;
+        }
+        let Some(message) = parsed.message else {
+            continue;
+        };
+
+        let Some(primary_span) = message
+            .spans
+            .iter()
+            .find(|span| span.is_primary && span.file_name.ends_with(&file_name))
+        else {
+            continue;
+        };
+
+        diagnostics.push(Diagnostic {
+            message: message.message,
+            level: message.level,
+            code: message.code.map(|c| c.code),
+            line_start: primary_span.line_start.saturating_sub(1),
+            line_end: primary_span.line_end.saturating_sub(1),
+            suggested_replacement: primary_span.suggested_replacement.clone(),
+        });
+    }
+
+    Ok(diagnostics)
+}
+
+/// Parse the backtick-quoted variant names out of a non-exhaustive match
+/// diagnostic's message (e.g. `` "patterns `B` and `C` not covered" ``) and
+/// generate a stub arm per missing variant as a single insertable block.
+fn fill_match_arms(message: &str) -> Option<String> {
+    let variants: Vec<&str> = message
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .filter(|s| !s.is_empty() && *s != "_")
+        .collect();
+
+    if variants.is_empty() {
+        return None;
+    }
+
+    let mut stub = String::new();
+    for variant in variants {
+        stub.push_str(&format!("    {} => todo!(),\n", variant));
+    }
+    Some(stub)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_match_arms_generates_a_stub_per_missing_variant() {
+        let message = "non-exhaustive patterns: `Color::Blue` and `Color::Green` not covered";
+        let stub = fill_match_arms(message).unwrap();
+        assert!(stub.contains("Color::Blue => todo!(),"));
+        assert!(stub.contains("Color::Green => todo!(),"));
+    }
+
+    #[test]
+    fn fill_match_arms_ignores_messages_without_variants() {
+        assert!(fill_match_arms("some unrelated error").is_none());
+    }
+
+    #[test]
+    fn diagnostic_in_range_becomes_a_completion() {
+        let provider = DiagnosticsProvider::new();
+        let request = CompletionRequest {
+            file_path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: "    let x = 1".to_string(),
+            cursor_position: crate::completion::Position {
+                line: 4,
+                column: 13,
+            },
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+        };
+        let diagnostics = vec![Diagnostic {
+            message: "unused variable: `x`".to_string(),
+            level: "warning".to_string(),
+            code: Some("unused_variables".to_string()),
+            line_start: 4,
+            line_end: 4,
+            suggested_replacement: Some("let _x = 1".to_string()),
+        }];
+
+        let completions =
+            provider.diagnostics_to_completions(&request, Pattern::Unknown, &diagnostics);
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "let _x = 1");
+        assert_eq!(completions[0].source, CompletionSource::Diagnostic);
+    }
+}
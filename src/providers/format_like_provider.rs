@@ -0,0 +1,303 @@
+//! Format-string argument expansion, mirroring rust-analyzer's
+//! `format_like` assist in reverse: turns `{expr}`-style inline captures
+//! inside a formatting macro call into positional `{}` placeholders plus a
+//! trailing, de-duplicated argument list.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::completion::{
+    Completion, CompletionProvider, CompletionRequest, CompletionSource, Pattern, PatternDetector,
+};
+
+/// Macro names this detector recognizes as formatting macros.
+const FORMAT_MACROS: &[&str] = &[
+    "print", "println", "eprint", "eprintln", "format", "write", "writeln", "panic", "assert",
+];
+
+/// Matches a (possibly still-being-typed) formatting macro call whose sole
+/// argument so far is a string literal, e.g. `println!("hi {name}"` or
+/// `format!("{x:?}")`.
+static FORMAT_MACRO_CALL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*(\w+!)\s*\(\s*"(.*)"\s*\)?\s*$"#).unwrap());
+
+/// Parse `line` as a formatting-macro call, returning the macro name
+/// (including `!`) and the literal string body if it matches.
+fn parse_format_macro_call(line: &str) -> Option<(String, String)> {
+    let captures = FORMAT_MACRO_CALL.captures(line)?;
+    let macro_with_bang = captures.get(1)?.as_str();
+    let name = macro_with_bang.trim_end_matches('!');
+    if !FORMAT_MACROS.contains(&name) {
+        return None;
+    }
+
+    Some((
+        macro_with_bang.to_string(),
+        captures.get(2)?.as_str().to_string(),
+    ))
+}
+
+/// Expand `{expr}`/`{expr:spec}` captures in `body` into `{}`/`{:spec}`
+/// placeholders, returning the rewritten body and the ordered,
+/// de-duplicated list of extracted expressions. `{}` and `{{`/`}}` are left
+/// untouched. Returns `None` if the braces in `body` are unbalanced.
+fn expand_format_like(body: &str) -> Option<(String, Vec<String>)> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut i = 0;
+    let mut out = String::new();
+    let mut args: Vec<String> = Vec::new();
+
+    while i < chars.len() {
+        match chars[i] {
+            '{' if chars.get(i + 1) == Some(&'{') => {
+                out.push_str("{{");
+                i += 2;
+            }
+            '{' => {
+                let close = chars[i + 1..].iter().position(|&c| c == '}')? + i + 1;
+                let inner: String = chars[i + 1..close].iter().collect();
+
+                match inner.find(':') {
+                    None if inner.is_empty() => out.push_str("{}"),
+                    None => {
+                        push_capture(&mut out, &mut args, inner.trim());
+                    }
+                    Some(spec_at) => {
+                        let (expr, spec) = inner.split_at(spec_at);
+                        let expr = expr.trim();
+                        if expr.is_empty() {
+                            // Bare format spec, e.g. `{:>8}` - already positional.
+                            out.push('{');
+                            out.push_str(&inner);
+                            out.push('}');
+                        } else {
+                            out.push('{');
+                            out.push_str(spec);
+                            out.push('}');
+                            if !args.contains(&expr.to_string()) {
+                                args.push(expr.to_string());
+                            }
+                        }
+                    }
+                }
+
+                i = close + 1;
+            }
+            '}' if chars.get(i + 1) == Some(&'}') => {
+                out.push_str("}}");
+                i += 2;
+            }
+            '}' => return None, // unbalanced closing brace
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Some((out, args))
+}
+
+fn push_capture(out: &mut String, args: &mut Vec<String>, expr: &str) {
+    out.push_str("{}");
+    if !args.contains(&expr.to_string()) {
+        args.push(expr.to_string());
+    }
+}
+
+/// Detects a formatting-macro call whose string literal contains inline
+/// `{expr}` captures to expand.
+pub struct FormatLikeDetector;
+
+impl PatternDetector for FormatLikeDetector {
+    fn detect_pattern(&self, line: &str, language: &str) -> Pattern {
+        if language != "rust" {
+            return Pattern::Unknown;
+        }
+
+        match parse_format_macro_call(line) {
+            Some((_, body))
+                if expand_format_like(&body).is_some_and(|(_, args)| !args.is_empty()) =>
+            {
+                Pattern::FormatLike
+            }
+            _ => Pattern::Unknown,
+        }
+    }
+
+    fn get_template(&self, _pattern: Pattern, _language: &str) -> Option<String> {
+        // The rewritten macro call depends on the literal's contents, which
+        // this trait's (pattern, language) signature can't carry; the real
+        // rewrite happens in `FormatLikeProvider::complete`.
+        None
+    }
+}
+
+/// Format-string argument expansion completion provider.
+pub struct FormatLikeProvider {
+    detector: FormatLikeDetector,
+    enabled: bool,
+}
+
+impl FormatLikeProvider {
+    pub fn new() -> Self {
+        Self {
+            detector: FormatLikeDetector,
+            enabled: true,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Default for FormatLikeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionProvider for FormatLikeProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        if self
+            .detector
+            .detect_pattern(&request.current_line, &request.language)
+            != Pattern::FormatLike
+        {
+            return Ok(Vec::new());
+        }
+
+        let Some((macro_name, body)) = parse_format_macro_call(&request.current_line) else {
+            return Ok(Vec::new());
+        };
+        let Some((rewritten_body, args)) = expand_format_like(&body) else {
+            return Ok(Vec::new());
+        };
+        if args.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut text = format!("{macro_name}(\"{rewritten_body}\"");
+        for arg in &args {
+            text.push_str(", ");
+            text.push_str(arg);
+        }
+        text.push(')');
+
+        let completion = Completion {
+            text,
+            cursor_offset: 0,
+            confidence: 0.85,
+            source: CompletionSource::Static,
+            replace_range: Some((0, request.current_line.chars().count())),
+            metadata: Some(serde_json::json!({
+                "pattern": "format_like",
+                "macro": macro_name,
+                "args": args,
+            })),
+        };
+
+        Ok(vec![completion])
+    }
+
+    fn name(&self) -> &str {
+        "format-like"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::completion::Position;
+
+    fn request(current_line: &str) -> CompletionRequest {
+        CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: current_line.to_string(),
+            cursor_position: Position {
+                line: 0,
+                column: current_line.chars().count(),
+            },
+            context_before: vec![],
+            context_after: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn expands_single_capture() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"println!("hello {name}")"#))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, r#"println!("hello {}", name)"#);
+    }
+
+    #[tokio::test]
+    async fn preserves_format_spec() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"format!("{x:?}")"#))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, r#"format!("{:?}", x)"#);
+    }
+
+    #[tokio::test]
+    async fn dedupes_repeated_expression() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"format!("{x} and {x}")"#))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, r#"format!("{} and {}", x)"#);
+    }
+
+    #[tokio::test]
+    async fn leaves_bare_placeholders_and_escapes_untouched() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"println!("{{literal}} {} {val}")"#))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, r#"println!("{{literal}} {} {}", val)"#);
+    }
+
+    #[tokio::test]
+    async fn unbalanced_braces_yield_no_completion() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"println!("{oops")"#))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn no_captures_yields_no_completion() {
+        let provider = FormatLikeProvider::new();
+        let completions = provider
+            .complete(&request(r#"println!("hello world")"#))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+}
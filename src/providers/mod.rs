@@ -2,6 +2,18 @@
 //!
 //! This module contains different completion providers (static, LLM, etc.)
 
+pub mod diagnostics_provider;
+pub mod format_like_provider;
+pub mod postfix_provider;
 pub mod static_provider;
+pub mod trait_impl_provider;
+pub mod wasm_plugin;
 
-pub use static_provider::StaticPatternProvider;
+pub use diagnostics_provider::DiagnosticsProvider;
+pub use format_like_provider::{FormatLikeDetector, FormatLikeProvider};
+pub use postfix_provider::{PostfixPatternDetector, PostfixPatternProvider};
+pub use static_provider::{PatternRule, StaticPatternProvider};
+pub use trait_impl_provider::{TraitImplDetector, TraitImplProvider};
+pub use wasm_plugin::{
+    load_plugins, PluginManifest, WasmPlugin, WasmPluginDetector, WasmPluginProvider,
+};
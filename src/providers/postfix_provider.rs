@@ -0,0 +1,313 @@
+//! Postfix-completion detection, mirroring rust-analyzer's postfix
+//! templates: a trailing `.keyword` on the current line wraps the
+//! expression to its left, e.g. `foo.if` -> `if foo {\n    \n}`.
+
+use anyhow::Result;
+
+use crate::completion::{
+    Completion, CompletionProvider, CompletionRequest, CompletionSource, Pattern, PatternDetector,
+};
+
+/// Placeholder substituted with the parsed receiver expression inside a
+/// postfix template.
+const RECEIVER_PLACEHOLDER: &str = "$RECEIVER$";
+
+/// Keywords recognized after a trailing `.` for a given language.
+fn known_keywords(language: &str) -> &'static [&'static str] {
+    match language {
+        "rust" => &["if", "while", "match", "dbg", "ref", "ok", "some", "let"],
+        "python" => &["if"],
+        "javascript" | "typescript" => &["if"],
+        _ => &[],
+    }
+}
+
+/// A trailing `<receiver>.<keyword>` span found on a line.
+struct PostfixMatch {
+    /// Column where the receiver expression starts.
+    receiver_start: usize,
+    /// Column of the `.` separating receiver and keyword.
+    dot_pos: usize,
+    /// Column just past the keyword (i.e. where the cursor sits).
+    end: usize,
+    keyword: String,
+}
+
+/// Scan `line` backward from `column` for a trailing `.<ident>`, then walk
+/// further backward from the `.` to find the start of the receiver
+/// expression: balance `)`/`(`, `]`/`[`, `}`/`{`, stopping at an unbalanced
+/// opener or a separator (`;`, `,`, `=`, or whitespace at bracket depth 0).
+fn parse_trailing_postfix(line: &str, column: usize) -> Option<PostfixMatch> {
+    let chars: Vec<char> = line.chars().collect();
+    let end = column.min(chars.len());
+
+    let mut i = end;
+    while i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_') {
+        i -= 1;
+    }
+    let keyword_start = i;
+    if keyword_start == 0 || chars[keyword_start - 1] != '.' {
+        return None;
+    }
+
+    let keyword: String = chars[keyword_start..end].iter().collect();
+    if keyword.is_empty() {
+        return None;
+    }
+    let dot_pos = keyword_start - 1;
+
+    let mut depth: i32 = 0;
+    let mut start = dot_pos;
+    while start > 0 {
+        let c = chars[start - 1];
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth == 0 => break,
+            '(' | '[' | '{' => depth -= 1,
+            ';' | ',' | '=' if depth == 0 => break,
+            c if c.is_whitespace() && depth == 0 => break,
+            _ => {}
+        }
+        start -= 1;
+    }
+
+    if start == dot_pos {
+        return None; // empty receiver, e.g. ".if" at the start of the line
+    }
+
+    Some(PostfixMatch {
+        receiver_start: start,
+        dot_pos,
+        end,
+        keyword,
+    })
+}
+
+/// Detects a trailing `.keyword` postfix-completion trigger. Assumes, like
+/// [`crate::providers::static_provider::StaticPatternDetector`], that `line`
+/// is the text up to the cursor.
+pub struct PostfixPatternDetector;
+
+impl PatternDetector for PostfixPatternDetector {
+    fn detect_pattern(&self, line: &str, language: &str) -> Pattern {
+        let keywords = known_keywords(language);
+        if keywords.is_empty() {
+            return Pattern::Unknown;
+        }
+
+        match parse_trailing_postfix(line, line.chars().count()) {
+            Some(postfix) if keywords.contains(&postfix.keyword.as_str()) => Pattern::Postfix {
+                keyword: postfix.keyword,
+            },
+            _ => Pattern::Unknown,
+        }
+    }
+
+    fn get_template(&self, pattern: Pattern, language: &str) -> Option<String> {
+        let Pattern::Postfix { keyword } = pattern else {
+            return None;
+        };
+
+        let r = RECEIVER_PLACEHOLDER;
+        match (language, keyword.as_str()) {
+            ("rust", "if") => Some(format!("if {r} {{\n    \n}}")),
+            ("rust", "while") => Some(format!("while {r} {{\n    \n}}")),
+            ("rust", "match") => Some(format!("match {r} {{\n    \n}}")),
+            ("rust", "dbg") => Some(format!("dbg!({r})")),
+            ("rust", "ref") => Some(format!("&{r}")),
+            ("rust", "ok") => Some(format!("Ok({r})")),
+            ("rust", "some") => Some(format!("Some({r})")),
+            ("rust", "let") => Some(format!("let $0 = {r};")),
+            ("python", "if") => Some(format!("if {r}:\n    ")),
+            ("javascript" | "typescript", "if") => Some(format!("if ({r}) {{\n    \n}}")),
+            _ => None,
+        }
+    }
+}
+
+/// Postfix-template completion provider
+pub struct PostfixPatternProvider {
+    detector: PostfixPatternDetector,
+    enabled: bool,
+}
+
+impl PostfixPatternProvider {
+    pub fn new() -> Self {
+        Self {
+            detector: PostfixPatternDetector,
+            enabled: true,
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Default for PostfixPatternProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionProvider for PostfixPatternProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        let keywords = known_keywords(&request.language);
+        if keywords.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let Some(postfix) =
+            parse_trailing_postfix(&request.current_line, request.cursor_position.column)
+        else {
+            return Ok(Vec::new());
+        };
+
+        if !keywords.contains(&postfix.keyword.as_str()) {
+            return Ok(Vec::new());
+        }
+
+        let receiver: String = request
+            .current_line
+            .chars()
+            .skip(postfix.receiver_start)
+            .take(postfix.dot_pos - postfix.receiver_start)
+            .collect();
+
+        let pattern = Pattern::Postfix {
+            keyword: postfix.keyword.clone(),
+        };
+        let Some(template) = self.detector.get_template(pattern, &request.language) else {
+            return Ok(Vec::new());
+        };
+
+        let text = template.replace(RECEIVER_PLACEHOLDER, &receiver);
+        let cursor_offset = match postfix.keyword.as_str() {
+            "if" | "while" | "match" => -2, // Move cursor inside the block
+            _ => 0,
+        };
+
+        let completion = Completion {
+            text,
+            cursor_offset,
+            confidence: 0.85,
+            source: CompletionSource::Static,
+            replace_range: Some((postfix.receiver_start, postfix.end)),
+            metadata: Some(serde_json::json!({
+                "pattern": "postfix",
+                "keyword": postfix.keyword,
+                "receiver": receiver,
+            })),
+        };
+
+        Ok(vec![completion])
+    }
+
+    fn name(&self) -> &str {
+        "postfix-pattern"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::completion::Position;
+
+    fn request(current_line: &str, language: &str) -> CompletionRequest {
+        let column = current_line.chars().count();
+        CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: language.to_string(),
+            current_line: current_line.to_string(),
+            cursor_position: Position { line: 0, column },
+            context_before: vec![],
+            context_after: vec![],
+        }
+    }
+
+    #[test]
+    fn detects_known_keyword() {
+        let detector = PostfixPatternDetector;
+        assert_eq!(
+            detector.detect_pattern("result.ok", "rust"),
+            Pattern::Postfix {
+                keyword: "ok".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_keyword() {
+        let detector = PostfixPatternDetector;
+        assert_eq!(
+            detector.detect_pattern("result.clone", "rust"),
+            Pattern::Unknown
+        );
+    }
+
+    #[tokio::test]
+    async fn if_postfix_wraps_simple_receiver() {
+        let provider = PostfixPatternProvider::new();
+        let completions = provider
+            .complete(&request("ready.if", "rust"))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "if ready {\n    \n}");
+        assert_eq!(completions[0].replace_range, Some((0, 8)));
+    }
+
+    #[tokio::test]
+    async fn dbg_postfix_wraps_function_call_receiver() {
+        let provider = PostfixPatternProvider::new();
+        let completions = provider
+            .complete(&request("compute(a, b).dbg", "rust"))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "dbg!(compute(a, b))");
+        assert_eq!(completions[0].replace_range, Some((0, 17)));
+    }
+
+    #[tokio::test]
+    async fn stops_receiver_scan_at_assignment() {
+        let provider = PostfixPatternProvider::new();
+        let completions = provider
+            .complete(&request("let x = value.some", "rust"))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "Some(value)");
+    }
+
+    #[tokio::test]
+    async fn python_if_uses_colon_block() {
+        let provider = PostfixPatternProvider::new();
+        let completions = provider
+            .complete(&request("ready.if", "python"))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "if ready:\n    ");
+    }
+
+    #[tokio::test]
+    async fn unknown_keyword_yields_no_completions() {
+        let provider = PostfixPatternProvider::new();
+        let completions = provider
+            .complete(&request("value.clone", "rust"))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+}
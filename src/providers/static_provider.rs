@@ -2,13 +2,195 @@
 //!
 //! This provider detects common code patterns and provides static completions
 
-use anyhow::Result;
-use regex::Regex;
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
 
 use crate::completion::{
     Completion, CompletionProvider, CompletionRequest, CompletionSource, Pattern, PatternDetector,
 };
 
+/// A user-defined trigger -> template completion rule, loaded from a rules
+/// file via `StaticPatternProvider::with_rules` and matched ahead of the
+/// built-in patterns. `trigger` is a glob (`*`, `**`, `?`) matched against
+/// the line up to the cursor; captured wildcards are substitutable into
+/// `template` via `$1`, `$2`, ....
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatternRule {
+    pub trigger: String,
+    pub language: String,
+    pub template: String,
+    #[serde(default)]
+    pub cursor_offset: i32,
+    #[serde(default = "default_rule_confidence")]
+    pub confidence: f32,
+}
+
+fn default_rule_confidence() -> f32 {
+    0.8
+}
+
+/// A `PatternRule` with its glob trigger compiled to a regex.
+struct CompiledRule {
+    rule: PatternRule,
+    regex: Regex,
+}
+
+/// Translate a glob trigger into an anchored, capturing regex the same way
+/// Mercurial's filepatterns glob-to-regex conversion does: escape regex
+/// metacharacters first, then replace the escaped glob tokens - longest
+/// first, so `**` isn't eaten by the `*` rule - with their regex
+/// equivalents, then anchor to the start (allowing for leading
+/// indentation) and end of the line, so the trigger must match the whole
+/// prefix typed so far rather than a fragment of it.
+fn compile_glob_trigger(trigger: &str) -> Regex {
+    let escaped = regex::escape(trigger);
+    let translated = escaped
+        .replace(r"\*\*", "(.*)")
+        .replace(r"\*", r"([^\s]*)")
+        .replace(r"\?", "(.)");
+
+    Regex::new(&format!(r"^\s*{translated}$")).expect("glob-derived regex is always valid")
+}
+
+/// Substitute `$1`, `$2`, ... in `template` with the corresponding capture
+/// group from `captures`. An index with no matching group is left empty.
+fn substitute_captures(template: &str, captures: &Captures) -> String {
+    let chars: Vec<char> = template.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '$' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()) {
+            let mut end = i + 1;
+            while chars.get(end).is_some_and(|c| c.is_ascii_digit()) {
+                end += 1;
+            }
+            let index: usize = chars[i + 1..end]
+                .iter()
+                .collect::<String>()
+                .parse()
+                .unwrap();
+            if let Some(m) = captures.get(index) {
+                out.push_str(m.as_str());
+            }
+            i = end;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Load rules from a TOML or JSON file, picking the format by extension
+/// (anything other than `.toml` is parsed as JSON), and compile each
+/// trigger's glob to a regex. A JSON file holds a top-level array of rules;
+/// a TOML file holds them as `[[rules]]` tables.
+fn load_rules(path: &Path) -> Result<Vec<CompiledRule>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read rules file {}", path.display()))?;
+
+    let rules: Vec<PatternRule> = if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+        #[derive(Deserialize)]
+        struct TomlRules {
+            rules: Vec<PatternRule>,
+        }
+
+        toml::from_str::<TomlRules>(&contents)
+            .map(|file| file.rules)
+            .with_context(|| format!("invalid rules file {}", path.display()))?
+    } else {
+        serde_json::from_str(&contents)
+            .with_context(|| format!("invalid rules file {}", path.display()))?
+    };
+
+    Ok(rules
+        .into_iter()
+        .map(|rule| {
+            let regex = compile_glob_trigger(&rule.trigger);
+            CompiledRule { rule, regex }
+        })
+        .collect())
+}
+
+/// Bundled syntax definitions, loaded once. Used to gate completions out of
+/// string/comment context; languages it doesn't recognize just skip the
+/// gate (see `StaticPatternProvider::in_suppressed_scope`).
+static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+
+/// Scope-name prefixes that mean the cursor sits inside text a pattern
+/// detector shouldn't fire on, e.g. a `//` comment containing `fn main`.
+const SUPPRESSED_SCOPES: &[&str] = &["string", "comment", "constant.character.escape"];
+
+fn scope_is_suppressed(scope_name: &str) -> bool {
+    SUPPRESSED_SCOPES
+        .iter()
+        .any(|prefix| scope_name == *prefix || scope_name.starts_with(&format!("{prefix}.")))
+}
+
+/// Rust patterns to check, in priority order. Compiled once on first use
+/// rather than per `detect_pattern` call, since completion runs on every
+/// keystroke.
+static RUST_PATTERNS: Lazy<Vec<(Pattern, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Pattern::FunctionStart,
+            Regex::new(r"^\s*(?:pub\s+)?(?:async\s+)?fn\s+\w+").unwrap(),
+        ),
+        (Pattern::IfStatement, Regex::new(r"^\s*if\s+").unwrap()),
+        (Pattern::ForLoop, Regex::new(r"^\s*for\s+").unwrap()),
+        (Pattern::WhileLoop, Regex::new(r"^\s*while\s+").unwrap()),
+        (
+            Pattern::StructDef,
+            Regex::new(r"^\s*(?:pub\s+)?struct\s+\w+").unwrap(),
+        ),
+        (
+            Pattern::ImplBlock,
+            Regex::new(r"^\s*impl(?:<[^>]+>)?\s+").unwrap(),
+        ),
+        (
+            Pattern::MatchStatement,
+            Regex::new(r"^\s*match\s+").unwrap(),
+        ),
+    ]
+});
+
+/// Python patterns to check, in priority order.
+static PYTHON_PATTERNS: Lazy<Vec<(Pattern, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Pattern::FunctionStart,
+            Regex::new(r"^\s*(?:async\s+)?def\s+\w+").unwrap(),
+        ),
+        (Pattern::IfStatement, Regex::new(r"^\s*if\s+").unwrap()),
+        (Pattern::ForLoop, Regex::new(r"^\s*for\s+").unwrap()),
+        (Pattern::WhileLoop, Regex::new(r"^\s*while\s+").unwrap()),
+        (Pattern::StructDef, Regex::new(r"^\s*class\s+\w+").unwrap()),
+    ]
+});
+
+/// JavaScript/TypeScript patterns to check, in priority order.
+static JS_PATTERNS: Lazy<Vec<(Pattern, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Pattern::FunctionStart,
+            Regex::new(
+                r"^\s*(?:async\s+)?(?:function\s+\w+|const\s+\w+\s*=\s*(?:async\s+)?\([^)]*\)\s*=>)",
+            )
+            .unwrap(),
+        ),
+        (Pattern::IfStatement, Regex::new(r"^\s*if\s*\(").unwrap()),
+        (Pattern::ForLoop, Regex::new(r"^\s*for\s*\(").unwrap()),
+        (Pattern::WhileLoop, Regex::new(r"^\s*while\s*\(").unwrap()),
+    ]
+});
+
 /// Static pattern detector
 pub struct StaticPatternDetector;
 
@@ -57,119 +239,40 @@ impl PatternDetector for StaticPatternDetector {
 
 impl StaticPatternDetector {
     fn detect_rust_pattern(&self, line: &str) -> Pattern {
-        // Function definition
-        if Regex::new(r"^\s*(?:pub\s+)?(?:async\s+)?fn\s+\w+")
-            .unwrap()
-            .is_match(line)
-        {
-            // Check if line doesn't already end with {
-            if !line.contains('{') {
-                return Pattern::FunctionStart;
-            }
-        }
-
-        // If statement
-        if Regex::new(r"^\s*if\s+").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::IfStatement;
-        }
-
-        // For loop
-        if Regex::new(r"^\s*for\s+").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::ForLoop;
-        }
-
-        // While loop
-        if Regex::new(r"^\s*while\s+").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::WhileLoop;
+        // None of these patterns apply once the block is already opened.
+        if line.contains('{') {
+            return Pattern::Unknown;
         }
 
-        // Struct definition
-        if Regex::new(r"^\s*(?:pub\s+)?struct\s+\w+")
-            .unwrap()
-            .is_match(line)
-            && !line.contains('{')
-        {
-            return Pattern::StructDef;
-        }
-
-        // Impl block
-        if Regex::new(r"^\s*impl(?:<[^>]+>)?\s+")
-            .unwrap()
-            .is_match(line)
-            && !line.contains('{')
-        {
-            return Pattern::ImplBlock;
-        }
-
-        // Match statement
-        if Regex::new(r"^\s*match\s+").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::MatchStatement;
-        }
-
-        Pattern::Unknown
+        RUST_PATTERNS
+            .iter()
+            .find(|(_, re)| re.is_match(line))
+            .map(|(pattern, _)| pattern.clone())
+            .unwrap_or(Pattern::Unknown)
     }
 
     fn detect_python_pattern(&self, line: &str) -> Pattern {
-        // Function definition
-        if Regex::new(r"^\s*(?:async\s+)?def\s+\w+")
-            .unwrap()
-            .is_match(line)
-            && !line.ends_with(':')
-        {
-            return Pattern::FunctionStart;
+        if line.ends_with(':') {
+            return Pattern::Unknown;
         }
 
-        // If statement
-        if Regex::new(r"^\s*if\s+").unwrap().is_match(line) && !line.ends_with(':') {
-            return Pattern::IfStatement;
-        }
-
-        // For loop
-        if Regex::new(r"^\s*for\s+").unwrap().is_match(line) && !line.ends_with(':') {
-            return Pattern::ForLoop;
-        }
-
-        // While loop
-        if Regex::new(r"^\s*while\s+").unwrap().is_match(line) && !line.ends_with(':') {
-            return Pattern::WhileLoop;
-        }
-
-        // Class definition
-        if Regex::new(r"^\s*class\s+\w+").unwrap().is_match(line) && !line.ends_with(':') {
-            return Pattern::StructDef;
-        }
-
-        Pattern::Unknown
+        PYTHON_PATTERNS
+            .iter()
+            .find(|(_, re)| re.is_match(line))
+            .map(|(pattern, _)| pattern.clone())
+            .unwrap_or(Pattern::Unknown)
     }
 
     fn detect_js_pattern(&self, line: &str) -> Pattern {
-        // Function definition
-        if Regex::new(
-            r"^\s*(?:async\s+)?(?:function\s+\w+|const\s+\w+\s*=\s*(?:async\s+)?\([^)]*\)\s*=>)",
-        )
-        .unwrap()
-        .is_match(line)
-            && !line.contains('{')
-        {
-            return Pattern::FunctionStart;
+        if line.contains('{') {
+            return Pattern::Unknown;
         }
 
-        // If statement
-        if Regex::new(r"^\s*if\s*\(").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::IfStatement;
-        }
-
-        // For loop
-        if Regex::new(r"^\s*for\s*\(").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::ForLoop;
-        }
-
-        // While loop
-        if Regex::new(r"^\s*while\s*\(").unwrap().is_match(line) && !line.contains('{') {
-            return Pattern::WhileLoop;
-        }
-
-        Pattern::Unknown
+        JS_PATTERNS
+            .iter()
+            .find(|(_, re)| re.is_match(line))
+            .map(|(pattern, _)| pattern.clone())
+            .unwrap_or(Pattern::Unknown)
     }
 }
 
@@ -177,6 +280,8 @@ impl StaticPatternDetector {
 pub struct StaticPatternProvider {
     detector: StaticPatternDetector,
     enabled: bool,
+    syntax_aware: bool,
+    rules: Vec<CompiledRule>,
 }
 
 impl StaticPatternProvider {
@@ -184,12 +289,97 @@ impl StaticPatternProvider {
         Self {
             detector: StaticPatternDetector,
             enabled: true,
+            syntax_aware: true,
+            rules: Vec::new(),
         }
     }
 
+    /// Construct a provider with its built-ins augmented by user-defined
+    /// rules loaded from `path` (TOML or JSON), matched ahead of the
+    /// built-in patterns.
+    pub fn with_rules(path: &Path) -> Result<Self> {
+        Ok(Self {
+            rules: load_rules(path)?,
+            ..Self::new()
+        })
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
+
+    /// Match `request.current_line` up to the cursor against the
+    /// user-defined rules, in file order, returning the first one whose
+    /// language and trigger match.
+    fn match_rule(&self, request: &CompletionRequest) -> Option<Completion> {
+        let cursor_prefix: String = request
+            .current_line
+            .chars()
+            .take(request.cursor_position.column)
+            .collect();
+
+        self.rules
+            .iter()
+            .filter(|compiled| compiled.rule.language == request.language)
+            .find_map(|compiled| {
+                let captures = compiled.regex.captures(&cursor_prefix)?;
+                Some(Completion {
+                    text: substitute_captures(&compiled.rule.template, &captures),
+                    cursor_offset: compiled.rule.cursor_offset,
+                    confidence: compiled.rule.confidence,
+                    source: CompletionSource::Static,
+                    replace_range: None,
+                    metadata: Some(serde_json::json!({
+                        "pattern": "rule",
+                        "trigger": compiled.rule.trigger,
+                    })),
+                })
+            })
+    }
+
+    /// Enable or disable the syntect-backed context gate that suppresses
+    /// completions when the cursor is inside a string literal, comment, or
+    /// character escape. On by default; turn it off for languages syntect
+    /// doesn't ship a syntax for, where the gate would just be a no-op.
+    pub fn with_syntax_awareness(mut self, enabled: bool) -> Self {
+        self.syntax_aware = enabled;
+        self
+    }
+
+    /// Re-lex `request.context_before` plus the line up to the cursor and
+    /// report whether the topmost scope at the cursor is one we suppress
+    /// completions in. Returns `false` (no gating) if syntect has no syntax
+    /// for `request.language`.
+    fn in_suppressed_scope(&self, request: &CompletionRequest) -> bool {
+        let Some(syntax) = SYNTAX_SET.find_syntax_by_token(&request.language) else {
+            return false;
+        };
+
+        let mut parse_state = ParseState::new(syntax);
+        let mut scope_stack = ScopeStack::new();
+
+        let cursor_prefix: String = request
+            .current_line
+            .chars()
+            .take(request.cursor_position.column)
+            .collect();
+
+        for line in request.context_before.iter().chain([&cursor_prefix]) {
+            let Ok(ops) = parse_state.parse_line(line, &SYNTAX_SET) else {
+                return false;
+            };
+            for (_, op) in ops {
+                if scope_stack.apply(&op).is_err() {
+                    return false;
+                }
+            }
+        }
+
+        scope_stack
+            .as_slice()
+            .last()
+            .is_some_and(|scope| scope_is_suppressed(&scope.build_string()))
+    }
 }
 
 impl Default for StaticPatternProvider {
@@ -201,6 +391,14 @@ impl Default for StaticPatternProvider {
 #[async_trait::async_trait]
 impl CompletionProvider for StaticPatternProvider {
     async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        if self.syntax_aware && self.in_suppressed_scope(request) {
+            return Ok(Vec::new());
+        }
+
+        if let Some(completion) = self.match_rule(request) {
+            return Ok(vec![completion]);
+        }
+
         let pattern = self
             .detector
             .detect_pattern(&request.current_line, &request.language);
@@ -209,12 +407,16 @@ impl CompletionProvider for StaticPatternProvider {
             return Ok(Vec::new());
         }
 
-        if let Some(template) = self.detector.get_template(pattern, &request.language) {
+        if let Some(template) = self
+            .detector
+            .get_template(pattern.clone(), &request.language)
+        {
             let completion = Completion {
                 text: template,
                 cursor_offset: -2, // Move cursor inside the block
                 confidence: 0.8,
                 source: CompletionSource::Static,
+                replace_range: None,
                 metadata: Some(serde_json::json!({
                     "pattern": format!("{:?}", pattern),
                 })),
@@ -295,4 +497,102 @@ mod tests {
         assert_eq!(completions.len(), 1);
         assert_eq!(completions[0].source, CompletionSource::Static);
     }
+
+    #[tokio::test]
+    async fn suppresses_completion_inside_comment() {
+        let provider = StaticPatternProvider::new();
+        let request = CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: "// fn main".to_string(),
+            cursor_position: crate::completion::Position {
+                line: 0,
+                column: 10,
+            },
+            context_before: vec![],
+            context_after: vec![],
+        };
+
+        let completions = provider.complete(&request).await.unwrap();
+        assert!(completions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn suppresses_completion_inside_string_literal() {
+        let provider = StaticPatternProvider::new();
+        let request = CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: r#"let s = "fn main"#.to_string(),
+            cursor_position: crate::completion::Position {
+                line: 0,
+                column: 17,
+            },
+            context_before: vec![],
+            context_after: vec![],
+        };
+
+        let completions = provider.complete(&request).await.unwrap();
+        assert!(completions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn syntax_awareness_can_be_disabled() {
+        let provider = StaticPatternProvider::new().with_syntax_awareness(false);
+        let request = CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: "// fn main".to_string(),
+            cursor_position: crate::completion::Position {
+                line: 0,
+                column: 10,
+            },
+            context_before: vec![],
+            context_after: vec![],
+        };
+
+        let completions = provider.complete(&request).await.unwrap();
+        assert_eq!(completions.len(), 1);
+    }
+
+    #[test]
+    fn glob_trigger_captures_wildcards() {
+        let regex = compile_glob_trigger("impl ** for *");
+        let captures = regex.captures("impl Display for Foo").unwrap();
+
+        assert_eq!(&captures[1], "Display");
+        assert_eq!(&captures[2], "Foo");
+    }
+
+    #[tokio::test]
+    async fn user_rule_matches_ahead_of_built_ins() {
+        let provider = StaticPatternProvider {
+            rules: vec![CompiledRule {
+                regex: compile_glob_trigger("logit *"),
+                rule: PatternRule {
+                    trigger: "logit *".to_string(),
+                    language: "rust".to_string(),
+                    template: "println!(\"{}\", $1);".to_string(),
+                    cursor_offset: 0,
+                    confidence: 0.9,
+                },
+            }],
+            ..StaticPatternProvider::new()
+        };
+        let request = CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: "logit value".to_string(),
+            cursor_position: crate::completion::Position {
+                line: 0,
+                column: 11,
+            },
+            context_before: vec![],
+            context_after: vec![],
+        };
+
+        let completions = provider.complete(&request).await.unwrap();
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "println!(\"{}\", value);");
+    }
 }
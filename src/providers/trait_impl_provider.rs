@@ -0,0 +1,322 @@
+//! Trait-method-stub completion, analogous to rust-analyzer's
+//! `item_list/trait_impl` completions: when the cursor sits inside an
+//! `impl <Trait> for <Type> { ... }` block, offer one completion per
+//! required method of `Trait` that the block doesn't already define.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::completion::{
+    Completion, CompletionProvider, CompletionRequest, CompletionSource, Pattern, PatternDetector,
+};
+
+/// Matches an `impl <Trait> for <Type> {` line (generics on either side are
+/// skipped, not captured).
+static IMPL_TRAIT_FOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*impl(?:<[^>]*>)?\s+([A-Za-z_]\w*)(?:<[^>]*>)?\s+for\s+([A-Za-z_][\w:]*)")
+        .unwrap()
+});
+
+/// Matches a line defining a method, capturing its name.
+static FN_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:pub\s+)?(?:async\s+)?fn\s+(\w+)").unwrap());
+
+/// A line that's either empty or an as-yet-incomplete `fn` declaration -
+/// the sites where a trait-method stub makes sense to suggest.
+static PARTIAL_FN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:pub\s+)?(?:async\s+)?fn(?:\s+\w*)?$").unwrap());
+
+/// Required method signatures for a small, built-in set of std traits.
+/// Extend this list to support more traits.
+fn required_methods(trait_name: &str) -> &'static [&'static str] {
+    match trait_name {
+        "Iterator" => &["fn next(&mut self) -> Option<Self::Item>"],
+        "Display" => &["fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result"],
+        "Default" => &["fn default() -> Self"],
+        "From" => &["fn from(value: T) -> Self"],
+        "Drop" => &["fn drop(&mut self)"],
+        "PartialEq" => &["fn eq(&self, other: &Self) -> bool"],
+        _ => &[],
+    }
+}
+
+fn method_name(signature: &str) -> Option<&str> {
+    FN_NAME
+        .captures(signature)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str())
+}
+
+fn stub_template(signature: &str) -> String {
+    format!("{signature} {{\n    todo!()\n}}")
+}
+
+/// Whether `current_line` looks like a spot to suggest a stub from: either
+/// empty (cursor on its own line inside the block) or a partially-typed
+/// `fn` declaration.
+fn is_stub_site(current_line: &str) -> bool {
+    let trimmed = current_line.trim();
+    trimmed.is_empty() || PARTIAL_FN.is_match(trimmed)
+}
+
+/// The nearest enclosing `impl <Trait> for <Type> { ... }` found by
+/// scanning `context_before` upward from the line nearest the cursor.
+struct EnclosingImpl {
+    trait_name: String,
+    /// Index into `context_before` of the `impl ... {` line itself; method
+    /// names already present in the block are looked for after this index.
+    impl_line_index: usize,
+}
+
+/// Scan `context_before` (oldest line first, nearest the cursor last) from
+/// the bottom up, tracking brace balance, to find the `impl Trait for Type
+/// {` line that opens the block the cursor is currently inside. Returns
+/// `None` if the brace nesting runs out before such a line is found (e.g.
+/// the cursor isn't inside an `impl` block, or it's a plain `impl Type {`
+/// with no trait).
+fn find_enclosing_impl(context_before: &[String]) -> Option<EnclosingImpl> {
+    let mut depth: i32 = 0;
+
+    for (index, line) in context_before.iter().enumerate().rev() {
+        for c in line.chars() {
+            match c {
+                '}' => depth += 1,
+                '{' => depth -= 1,
+                _ => {}
+            }
+        }
+
+        if depth < 0 {
+            let captures = IMPL_TRAIT_FOR.captures(line)?;
+            return Some(EnclosingImpl {
+                trait_name: captures.get(1)?.as_str().to_string(),
+                impl_line_index: index,
+            });
+        }
+    }
+
+    None
+}
+
+/// Method names already defined between `impl_line_index` and the cursor.
+fn methods_already_present(context_before: &[String], impl_line_index: usize) -> Vec<&str> {
+    context_before[impl_line_index + 1..]
+        .iter()
+        .filter_map(|line| method_name(line))
+        .collect()
+}
+
+/// Detects an `impl <Trait> for <Type> {` opening on a single line. Full,
+/// context-aware detection of the cursor being *inside* such a block -
+/// which is what actually drives completions - needs `context_before` and
+/// happens in [`TraitImplProvider::complete`] instead; this only covers a
+/// trait name typed on the same line the detector is asked about.
+pub struct TraitImplDetector;
+
+impl PatternDetector for TraitImplDetector {
+    fn detect_pattern(&self, line: &str, language: &str) -> Pattern {
+        if language != "rust" {
+            return Pattern::Unknown;
+        }
+
+        match IMPL_TRAIT_FOR.captures(line) {
+            Some(captures) => Pattern::TraitImpl {
+                trait_name: captures[1].to_string(),
+            },
+            None => Pattern::Unknown,
+        }
+    }
+
+    fn get_template(&self, pattern: Pattern, language: &str) -> Option<String> {
+        let Pattern::TraitImpl { trait_name } = pattern else {
+            return None;
+        };
+        if language != "rust" {
+            return None;
+        }
+
+        let methods = required_methods(&trait_name);
+        if methods.is_empty() {
+            return None;
+        }
+
+        Some(
+            methods
+                .iter()
+                .map(|signature| stub_template(signature))
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        )
+    }
+}
+
+/// Trait-method-stub completion provider.
+pub struct TraitImplProvider {
+    enabled: bool,
+}
+
+impl TraitImplProvider {
+    pub fn new() -> Self {
+        Self { enabled: true }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Default for TraitImplProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionProvider for TraitImplProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        if request.language != "rust" || !is_stub_site(&request.current_line) {
+            return Ok(Vec::new());
+        }
+
+        let Some(enclosing) = find_enclosing_impl(&request.context_before) else {
+            return Ok(Vec::new());
+        };
+
+        let methods = required_methods(&enclosing.trait_name);
+        if methods.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let present = methods_already_present(&request.context_before, enclosing.impl_line_index);
+
+        let completions = methods
+            .iter()
+            .filter(|signature| method_name(signature).is_some_and(|name| !present.contains(&name)))
+            .map(|signature| Completion {
+                text: stub_template(signature),
+                cursor_offset: 0,
+                confidence: 0.8,
+                source: CompletionSource::Static,
+                replace_range: None,
+                metadata: Some(serde_json::json!({
+                    "pattern": "trait_impl",
+                    "trait": enclosing.trait_name,
+                    "method": method_name(signature),
+                })),
+            })
+            .collect();
+
+        Ok(completions)
+    }
+
+    fn name(&self) -> &str {
+        "trait-impl"
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::completion::Position;
+
+    fn request(context_before: Vec<&str>, current_line: &str) -> CompletionRequest {
+        CompletionRequest {
+            file_path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            current_line: current_line.to_string(),
+            cursor_position: Position {
+                line: context_before.len(),
+                column: current_line.chars().count(),
+            },
+            context_before: context_before.into_iter().map(String::from).collect(),
+            context_after: vec![],
+        }
+    }
+
+    #[test]
+    fn detects_impl_trait_for_line() {
+        let detector = TraitImplDetector;
+        assert_eq!(
+            detector.detect_pattern("impl Iterator for Counter {", "rust"),
+            Pattern::TraitImpl {
+                trait_name: "Iterator".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn stubs_missing_method_on_empty_line() {
+        let provider = TraitImplProvider::new();
+        let completions = provider
+            .complete(&request(vec!["impl Iterator for Counter {"], ""))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(
+            completions[0].text,
+            "fn next(&mut self) -> Option<Self::Item> {\n    todo!()\n}"
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_method_already_defined_in_block() {
+        let provider = TraitImplProvider::new();
+        let completions = provider
+            .complete(&request(
+                vec![
+                    "impl Iterator for Counter {",
+                    "    fn next(&mut self) -> Option<Self::Item> {",
+                    "        None",
+                    "    }",
+                ],
+                "",
+            ))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn matches_partial_fn_declaration() {
+        let provider = TraitImplProvider::new();
+        let completions = provider
+            .complete(&request(vec!["impl Default for Config {"], "fn de"))
+            .await
+            .unwrap();
+
+        assert_eq!(completions.len(), 1);
+        assert_eq!(
+            completions[0].text,
+            "fn default() -> Self {\n    todo!()\n}"
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_unrelated_line() {
+        let provider = TraitImplProvider::new();
+        let completions = provider
+            .complete(&request(vec!["impl Iterator for Counter {"], "let x = 1"))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn no_completions_outside_impl_block() {
+        let provider = TraitImplProvider::new();
+        let completions = provider
+            .complete(&request(vec!["fn standalone() {"], ""))
+            .await
+            .unwrap();
+
+        assert!(completions.is_empty());
+    }
+}
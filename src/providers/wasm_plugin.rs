@@ -0,0 +1,313 @@
+//! WASM-sandboxed completion plugins.
+//!
+//! Lets third parties ship `CompletionProvider`/`PatternDetector`
+//! implementations compiled to `wasm32-wasi` and loaded from a directory at
+//! startup instead of being baked into the binary. The host and guest agree
+//! on a small ABI: the host allocates space in the module's linear memory
+//! via an exported `alloc`, writes a JSON-encoded request into it, calls the
+//! guest's exported entry point (`complete`, `detect_pattern`, or
+//! `get_template`), then reads and `dealloc`s the JSON-encoded response the
+//! guest wrote back. The return value packs the response as `(ptr << 32) |
+//! len` so a single `u64` carries both.
+//!
+//! Each plugin gets its own `wasmtime` `Store` with a WASI context that only
+//! preopens the directories its manifest declares, so a plugin has no host
+//! filesystem or network access beyond what it was granted.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use wasmtime::{Engine, Instance, Linker, Module, Store, TypedFunc};
+use wasmtime_wasi::sync::WasiCtxBuilder;
+use wasmtime_wasi::WasiCtx;
+
+use crate::completion::{
+    Completion, CompletionProvider, CompletionRequest, Pattern, PatternDetector,
+};
+
+/// On-disk manifest shipped alongside a `<name>.wasm` module, declaring the
+/// capabilities the host should grant it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginManifest {
+    /// Display name, used as the `CompletionProvider::name()`.
+    pub name: String,
+    /// Host directories the plugin is allowed to see, pre-opened into its
+    /// WASI context under the same path. Empty by default, i.e. no FS access.
+    #[serde(default)]
+    pub preopen_dirs: Vec<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct DetectPatternRequest<'a> {
+    line: &'a str,
+    language: &'a str,
+}
+
+#[derive(Deserialize)]
+struct DetectPatternResponse {
+    pattern: Pattern,
+}
+
+#[derive(Serialize)]
+struct GetTemplateRequest<'a> {
+    pattern: Pattern,
+    language: &'a str,
+}
+
+#[derive(Deserialize)]
+struct GetTemplateResponse {
+    template: Option<String>,
+}
+
+/// A loaded, instantiated plugin module and the store it owns. Shared
+/// (behind a `Mutex`, since a `wasmtime::Store` isn't `Sync`) between the
+/// `CompletionProvider` and `PatternDetector` wrappers for the same plugin.
+struct LoadedPlugin {
+    name: String,
+    store: Mutex<Store<WasiCtx>>,
+    instance: Instance,
+}
+
+impl LoadedPlugin {
+    fn load(engine: &Engine, path: &Path, manifest: &PluginManifest) -> Result<Self> {
+        let module = Module::from_file(engine, path)
+            .with_context(|| format!("failed to compile plugin module {}", path.display()))?;
+
+        let mut wasi = WasiCtxBuilder::new();
+        for dir in &manifest.preopen_dirs {
+            wasi.preopened_dir(
+                wasmtime_wasi::sync::Dir::open_ambient_dir(
+                    dir,
+                    wasmtime_wasi::sync::ambient_authority(),
+                )
+                .with_context(|| format!("failed to open preopen_dir {}", dir.display()))?,
+                dir.clone(),
+            )?;
+        }
+        let mut store = Store::new(engine, wasi.build());
+
+        let mut linker: Linker<WasiCtx> = Linker::new(engine);
+        wasmtime_wasi::sync::add_to_linker(&mut linker, |ctx| ctx)?;
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .with_context(|| format!("failed to instantiate plugin {}", manifest.name))?;
+
+        Ok(Self {
+            name: manifest.name.clone(),
+            store: Mutex::new(store),
+            instance,
+        })
+    }
+
+    /// Call a guest export taking and returning a JSON payload.
+    fn call_json(&self, export_name: &str, request: &[u8]) -> Result<Vec<u8>> {
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|_| anyhow!("plugin '{}' store lock poisoned", self.name))?;
+        let store = &mut *store;
+
+        let memory = self
+            .instance
+            .get_memory(&mut *store, "memory")
+            .ok_or_else(|| anyhow!("plugin '{}' has no exported `memory`", self.name))?;
+        let alloc: TypedFunc<u32, u32> = self.instance.get_typed_func(&mut *store, "alloc")?;
+        let dealloc: TypedFunc<(u32, u32), ()> =
+            self.instance.get_typed_func(&mut *store, "dealloc")?;
+        let entry: TypedFunc<(u32, u32), u64> =
+            self.instance.get_typed_func(&mut *store, export_name)?;
+
+        let in_len = request.len() as u32;
+        let in_ptr = alloc.call(&mut *store, in_len)?;
+        memory.write(&mut *store, in_ptr as usize, request)?;
+
+        let packed = entry.call(&mut *store, (in_ptr, in_len));
+        dealloc.call(&mut *store, (in_ptr, in_len))?;
+        let packed = packed?;
+
+        let out_ptr = (packed >> 32) as u32 as usize;
+        let out_len = (packed & 0xFFFF_FFFF) as u32 as usize;
+
+        let mut buf = vec![0u8; out_len];
+        memory.read(&mut *store, out_ptr, &mut buf)?;
+        dealloc.call(&mut *store, (out_ptr as u32, out_len as u32))?;
+
+        Ok(buf)
+    }
+}
+
+/// `CompletionProvider` backed by a `<name>.wasm` plugin's exported
+/// `complete` function. `is_enabled()` reflects whether the module loaded
+/// and validated successfully, so a broken plugin is silently skipped by
+/// `CompletionEngine` rather than failing every request.
+pub struct WasmPluginProvider {
+    name: String,
+    plugin: Option<Arc<LoadedPlugin>>,
+}
+
+impl WasmPluginProvider {
+    fn loaded(plugin: Arc<LoadedPlugin>) -> Self {
+        Self {
+            name: plugin.name.clone(),
+            plugin: Some(plugin),
+        }
+    }
+
+    fn failed(name: String) -> Self {
+        Self { name, plugin: None }
+    }
+}
+
+#[async_trait::async_trait]
+impl CompletionProvider for WasmPluginProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Result<Vec<Completion>> {
+        let plugin = self
+            .plugin
+            .clone()
+            .ok_or_else(|| anyhow!("plugin '{}' failed to load", self.name))?;
+        let request_json = serde_json::to_vec(request)?;
+
+        let response_json =
+            tokio::task::spawn_blocking(move || plugin.call_json("complete", &request_json))
+                .await
+                .map_err(|e| anyhow!("plugin task panicked: {}", e))??;
+
+        let completions: Vec<Completion> = serde_json::from_slice(&response_json)
+            .with_context(|| format!("plugin '{}' returned malformed completions", self.name))?;
+        Ok(completions)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.plugin.is_some()
+    }
+}
+
+/// `PatternDetector` backed by the same `<name>.wasm` plugin's exported
+/// `detect_pattern`/`get_template` functions. Falls back to `Pattern::Unknown`
+/// / `None` if the plugin failed to load, so a broken detector degrades to a
+/// no-op rather than panicking the editor session.
+pub struct WasmPluginDetector {
+    plugin: Option<Arc<LoadedPlugin>>,
+}
+
+impl WasmPluginDetector {
+    fn call<Req: Serialize, Resp: for<'de> Deserialize<'de>>(
+        &self,
+        export_name: &str,
+        request: &Req,
+    ) -> Option<Resp> {
+        let plugin = self.plugin.as_ref()?;
+        let request_json = serde_json::to_vec(request).ok()?;
+        match plugin.call_json(export_name, &request_json) {
+            Ok(response_json) => serde_json::from_slice(&response_json).ok(),
+            Err(e) => {
+                tracing::warn!(
+                    "plugin '{}' export '{}' failed: {}",
+                    plugin.name,
+                    export_name,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+impl PatternDetector for WasmPluginDetector {
+    fn detect_pattern(&self, line: &str, language: &str) -> Pattern {
+        self.call::<_, DetectPatternResponse>(
+            "detect_pattern",
+            &DetectPatternRequest { line, language },
+        )
+        .map(|r| r.pattern)
+        .unwrap_or(Pattern::Unknown)
+    }
+
+    fn get_template(&self, pattern: Pattern, language: &str) -> Option<String> {
+        self.call::<_, GetTemplateResponse>(
+            "get_template",
+            &GetTemplateRequest { pattern, language },
+        )
+        .and_then(|r| r.template)
+    }
+}
+
+/// A successfully or unsuccessfully loaded plugin, paired as a provider and
+/// a detector over the same module so callers can register both with the
+/// `CompletionEngine` and a pattern-aware caller respectively.
+pub struct WasmPlugin {
+    pub provider: WasmPluginProvider,
+    pub detector: WasmPluginDetector,
+}
+
+/// Scan `directory` for `<name>.wasm` modules, reading each one's
+/// `<name>.manifest.json` when present (without one, the plugin is named
+/// after its file stem and gets no preopened directories), and instantiate
+/// each in its own sandboxed store.
+/// A plugin that fails to load is still returned (as a disabled provider
+/// and a no-op detector) so one bad module doesn't prevent the rest from
+/// loading; the failure is logged via `tracing::warn`.
+pub fn load_plugins(directory: &Path) -> Result<Vec<WasmPlugin>> {
+    let engine = Engine::default();
+    let mut plugins = Vec::new();
+
+    if !directory.exists() {
+        return Ok(plugins);
+    }
+
+    let entries = fs::read_dir(directory)
+        .with_context(|| format!("failed to read plugin directory {}", directory.display()))?;
+
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("wasm") {
+            continue;
+        }
+
+        let manifest_path = path.with_extension("manifest.json");
+        let fallback_name = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let manifest = match fs::read_to_string(&manifest_path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("invalid manifest {}", manifest_path.display())),
+            Err(_) => Ok(PluginManifest {
+                name: fallback_name.clone(),
+                preopen_dirs: Vec::new(),
+            }),
+        };
+
+        let loaded = manifest.and_then(|manifest| LoadedPlugin::load(&engine, &path, &manifest));
+
+        match loaded {
+            Ok(plugin) => {
+                let plugin = Arc::new(plugin);
+                plugins.push(WasmPlugin {
+                    provider: WasmPluginProvider::loaded(Arc::clone(&plugin)),
+                    detector: WasmPluginDetector {
+                        plugin: Some(plugin),
+                    },
+                });
+            }
+            Err(e) => {
+                tracing::warn!("failed to load plugin {}: {}", path.display(), e);
+                plugins.push(WasmPlugin {
+                    provider: WasmPluginProvider::failed(fallback_name),
+                    detector: WasmPluginDetector { plugin: None },
+                });
+            }
+        }
+    }
+
+    Ok(plugins)
+}
@@ -0,0 +1,170 @@
+//! Automatic provisioning and version pinning for `Local` command-backed
+//! servers.
+//!
+//! When a server's `command` is missing from `PATH`, or its pinned
+//! `version` differs from what was last installed, [`Provisioner`] runs the
+//! server's `install` step once and records the resolved version in a
+//! per-user cache so subsequent runs are a no-op.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::config::{MCPConfig, ServerConfig};
+
+/// Persisted record of installed versions, keyed by server name.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProvisionCache {
+    installed: HashMap<String, String>,
+}
+
+impl ProvisionCache {
+    fn cache_path() -> Result<PathBuf> {
+        let dir = dirs::cache_dir()
+            .ok_or_else(|| anyhow!("Could not determine a cache directory for this platform"))?
+            .join("rusty-mcp");
+        Ok(dir.join("provisioned.json"))
+    }
+
+    /// Load the cache from disk, starting empty if it doesn't exist yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::cache_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read provisioning cache {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    /// Persist the cache to disk, creating the cache directory if needed.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::cache_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn installed_version(&self, server_name: &str) -> Option<&str> {
+        self.installed.get(server_name).map(String::as_str)
+    }
+}
+
+/// Returns true when `server` needs (re)installation: its command is
+/// missing from `PATH`, or a pinned `version` doesn't match the cache.
+pub fn needs_provisioning(
+    server_name: &str,
+    server: &ServerConfig,
+    cache: &ProvisionCache,
+) -> bool {
+    match server {
+        ServerConfig::Local {
+            command, version, ..
+        } => {
+            if which::which(command).is_err() {
+                return true;
+            }
+            match version {
+                Some(pinned) => cache.installed_version(server_name) != Some(pinned.as_str()),
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+impl MCPConfig {
+    /// Servers that need provisioning before they can be connected to, per
+    /// [`needs_provisioning`].
+    pub fn servers_needing_provisioning(&self, cache: &ProvisionCache) -> Vec<&str> {
+        self.enabled_servers()
+            .filter(|(name, server)| needs_provisioning(name, server, cache))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Runs `install` steps for servers that need them and records the
+/// resolved version in a per-user cache.
+pub struct Provisioner {
+    cache: ProvisionCache,
+}
+
+impl Provisioner {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            cache: ProvisionCache::load()?,
+        })
+    }
+
+    /// Provision every enabled server in `config` that needs it.
+    pub fn provision_all(&mut self, config: &MCPConfig) -> Result<()> {
+        for (name, server) in config.enabled_servers() {
+            if needs_provisioning(name, server, &self.cache) {
+                self.provision(name, server)?;
+            }
+        }
+        self.cache.save()
+    }
+
+    /// Run the install step for a single server, if it has one.
+    fn provision(&mut self, name: &str, server: &ServerConfig) -> Result<()> {
+        let ServerConfig::Local {
+            command,
+            install,
+            version,
+            ..
+        } = server
+        else {
+            return Ok(());
+        };
+
+        let Some(install) = install else {
+            tracing::warn!(
+                "Server '{}' needs provisioning for command '{}' but has no install step configured",
+                name,
+                command
+            );
+            return Ok(());
+        };
+
+        // Move any in-use binary aside first so the installer doesn't hit
+        // "Text file busy" overwriting a binary that's currently running.
+        let previous_binary = which::which(command).ok();
+        if let Some(path) = &previous_binary {
+            let _ = std::fs::rename(path, path.with_extension("prev"));
+        }
+
+        tracing::info!("Provisioning server '{}' via: {}", name, install);
+        let status = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(install)
+            .status();
+
+        match status {
+            Ok(status) if status.success() => {
+                if let Some(path) = &previous_binary {
+                    let _ = std::fs::remove_file(path.with_extension("prev"));
+                }
+            }
+            _ => {
+                // Restore the previous binary so the server keeps working
+                // with whatever version was there before.
+                if let Some(path) = &previous_binary {
+                    let _ = std::fs::rename(path.with_extension("prev"), path);
+                }
+                return Err(anyhow!("Install step for server '{}' failed", name));
+            }
+        }
+
+        if let Some(pinned) = version {
+            self.cache.installed.insert(name.to_string(), pinned.clone());
+        }
+
+        Ok(())
+    }
+}
@@ -2,11 +2,22 @@
 //!
 //! This module implements a JSON-RPC server that communicates with Neovim
 //! over stdio. It handles completion requests and other MCP-related operations.
+//!
+//! Responses are written through a dedicated writer task fed by an `mpsc`
+//! channel rather than directly to stdout, so that server-initiated
+//! notifications (e.g. streaming completion results from
+//! `subscribe_completion`) can interleave with request/response traffic
+//! instead of waiting for the read loop to come back around.
 
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::io::{self, BufRead, Write};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
 use tracing::{debug, error, info, warn};
 
 use crate::client::MCPClient;
@@ -42,6 +53,34 @@ struct JsonRpcError {
     data: Option<Value>,
 }
 
+/// A server-initiated notification frame: no `id`, since nothing is
+/// replying to a request, just naming a `method` and its `params`. Used to
+/// push streaming completion results from `subscribe_completion`.
+#[derive(Debug, Clone, Serialize)]
+struct JsonRpcNotification {
+    jsonrpc: String,
+    method: String,
+    params: Value,
+}
+
+/// How JSON-RPC frames are delimited on the stdio stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramingMode {
+    /// One JSON value per line (the original, Neovim-facing transport).
+    LineDelimited,
+    /// The LSP/DAP base protocol: a `Content-Length: N\r\n\r\n` header
+    /// followed by exactly `N` bytes of UTF-8 JSON, with no delimiter
+    /// required inside the body. Lets header-framed front ends (e.g.
+    /// Helix's `helix-lsp`) talk to this server without a proxy.
+    ContentLength,
+}
+
+impl Default for FramingMode {
+    fn default() -> Self {
+        FramingMode::LineDelimited
+    }
+}
+
 impl JsonRpcError {
     fn parse_error() -> Self {
         Self {
@@ -74,81 +113,324 @@ impl JsonRpcError {
             data: None,
         }
     }
+
+    /// Request params failed validation/deserialization (JSON-RPC -32602).
+    /// `detail` (typically the serde error message) is surfaced in `data`
+    /// so the caller can show an actionable diagnostic instead of a bare
+    /// "Invalid params" string.
+    fn invalid_params(detail: String) -> Self {
+        Self {
+            code: -32602,
+            message: "Invalid params".to_string(),
+            data: Some(serde_json::json!({ "detail": detail })),
+        }
+    }
+
+    /// An MCP tool/resource call to `server` returned an error: the server
+    /// is unknown or not connected, or the call failed there. A tool result
+    /// flagged `is_error` is returned as a normal result, not through this.
+    /// Carries the server name and underlying failure message in `data`
+    /// so Neovim can distinguish "unknown server" / transport errors from
+    /// a generic internal fault.
+    fn mcp_error(server: &str, detail: String) -> Self {
+        Self {
+            code: -32001,
+            message: format!("MCP operation failed for server '{}'", server),
+            data: Some(serde_json::json!({ "server": server, "detail": detail })),
+        }
+    }
+}
+
+/// Carries enough structure through an `anyhow::Error` for `handle_request`
+/// to build a precise JSON-RPC error object instead of collapsing every
+/// handler failure to `internal_error`. Handlers construct one of these and
+/// return it via `anyhow::Error::from`; `handle_request` recovers it with
+/// `downcast_ref`.
+#[derive(Debug)]
+enum HandlerError {
+    /// Params didn't match the shape the handler expected.
+    InvalidParams(String),
+    /// A call to a specific MCP server failed.
+    McpFailure { server: String, detail: String },
 }
 
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandlerError::InvalidParams(detail) => write!(f, "invalid params: {}", detail),
+            HandlerError::McpFailure { server, detail } => {
+                write!(f, "MCP call to '{}' failed: {}", server, detail)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
 /// JSON-RPC server for handling Neovim requests
 pub struct JsonRpcServer {
-    completion_engine: CompletionEngine,
+    completion_engine: Arc<CompletionEngine>,
     mcp_client: Option<MCPClient>,
+    /// Sender side of the writer task's channel; populated when `run`
+    /// starts so request handlers (and spawned subscription tasks) can
+    /// push frames to stdout without owning it themselves.
+    writer_tx: Mutex<Option<mpsc::UnboundedSender<Value>>>,
+    /// Active `subscribe_completion` subscriptions, keyed by subscription
+    /// id, so `unsubscribe` can cancel the backing task.
+    subscriptions: Arc<Mutex<HashMap<u64, tokio::task::JoinHandle<()>>>>,
+    next_subscription_id: AtomicU64,
+    framing: FramingMode,
 }
 
 impl JsonRpcServer {
     /// Create a new JSON-RPC server
     pub fn new(completion_engine: CompletionEngine, mcp_client: Option<MCPClient>) -> Self {
         Self {
-            completion_engine,
+            completion_engine: Arc::new(completion_engine),
             mcp_client,
+            writer_tx: Mutex::new(None),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            next_subscription_id: AtomicU64::new(0),
+            framing: FramingMode::default(),
         }
     }
 
+    /// Select the stdio framing mode (default: [`FramingMode::LineDelimited`])
+    pub fn with_framing(mut self, framing: FramingMode) -> Self {
+        self.framing = framing;
+        self
+    }
+
     /// Run the JSON-RPC server (blocking)
     ///
-    /// This will read from stdin and write to stdout in a loop
+    /// This will read from stdin and write to stdout in a loop. Writes go
+    /// through a dedicated writer task so that notifications pushed by
+    /// background subscription tasks can interleave with request replies.
     pub async fn run(&mut self) -> Result<()> {
         info!("Starting JSON-RPC server on stdio");
 
+        let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+        *self.writer_tx.lock().await = Some(tx);
+
+        let framing = self.framing;
+        let writer = tokio::spawn(async move {
+            let mut stdout = io::stdout();
+            while let Some(frame) = rx.recv().await {
+                match serde_json::to_string(&frame) {
+                    Ok(frame_json) => {
+                        debug!("Sending frame: {}", frame_json);
+                        if let Err(e) = Self::write_frame(&mut stdout, framing, &frame_json) {
+                            error!("Failed to write to stdout: {}", e);
+                            break;
+                        }
+                    }
+                    Err(e) => error!("Failed to serialize frame: {}", e),
+                }
+            }
+        });
+
         let stdin = io::stdin();
-        let mut stdout = io::stdout();
-        let reader = stdin.lock();
+        let mut reader = stdin.lock();
 
-        for line in reader.lines() {
-            let line = match line {
-                Ok(l) => l,
+        loop {
+            let request_body = match Self::read_frame(&mut reader, self.framing) {
+                Ok(Some(body)) => body,
+                Ok(None) => break,
                 Err(e) => {
-                    error!("Failed to read line from stdin: {}", e);
+                    error!("Failed to read frame from stdin: {}", e);
                     break;
                 }
             };
 
-            // Skip empty lines
-            if line.trim().is_empty() {
+            // Skip empty line-delimited input
+            if request_body.trim().is_empty() {
                 continue;
             }
 
-            debug!("Received request: {}", line);
+            debug!("Received request: {}", request_body);
 
-            // Parse and handle request
-            let response = self.handle_request_line(&line).await;
+            // Parse and handle request (a single object, or a JSON-RPC batch array)
+            let response = self.handle_request_line(&request_body).await;
 
-            // Serialize and send response
-            let response_json = serde_json::to_string(&response)?;
-            debug!("Sending response: {}", response_json);
-
-            writeln!(stdout, "{}", response_json)?;
-            stdout.flush()?;
+            // Send the response, if the frame produced one
+            if let Some(response) = response {
+                let sender = self.writer_tx.lock().await.clone();
+                match sender {
+                    Some(sender) if sender.send(response).is_ok() => {}
+                    _ => {
+                        error!("Writer task is gone; dropping response");
+                        break;
+                    }
+                }
+            }
         }
 
+        // Drop our sender so the writer task can drain and exit once any
+        // in-flight subscription tasks (which hold their own clones) finish.
+        *self.writer_tx.lock().await = None;
+        let _ = writer.await;
+
         info!("JSON-RPC server shutting down");
         Ok(())
     }
 
-    /// Handle a single request line
-    async fn handle_request_line(&mut self, line: &str) -> JsonRpcResponse {
-        // Parse JSON-RPC request
-        let request: JsonRpcRequest = match serde_json::from_str(line) {
+    /// Read one request body from `reader` according to `framing`. Returns
+    /// `Ok(None)` at EOF.
+    fn read_frame(reader: &mut impl BufRead, framing: FramingMode) -> io::Result<Option<String>> {
+        match framing {
+            FramingMode::LineDelimited => {
+                let mut line = String::new();
+                if reader.read_line(&mut line)? == 0 {
+                    return Ok(None);
+                }
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Ok(Some(line))
+            }
+            FramingMode::ContentLength => {
+                let mut content_length: Option<usize> = None;
+                loop {
+                    let mut header_line = String::new();
+                    if reader.read_line(&mut header_line)? == 0 {
+                        return Ok(None);
+                    }
+                    let header_line = header_line.trim_end_matches(['\r', '\n']);
+                    if header_line.is_empty() {
+                        break;
+                    }
+                    if let Some(value) = header_line.strip_prefix("Content-Length:") {
+                        content_length = value.trim().parse().ok();
+                    }
+                }
+
+                let content_length = content_length.ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+                })?;
+
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body)?;
+                String::from_utf8(body)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+
+    /// Write one response/notification frame to `writer` according to `framing`.
+    fn write_frame(writer: &mut impl Write, framing: FramingMode, body: &str) -> io::Result<()> {
+        match framing {
+            FramingMode::LineDelimited => writeln!(writer, "{}", body)?,
+            FramingMode::ContentLength => {
+                write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?
+            }
+        }
+        writer.flush()
+    }
+
+    /// Build a server-initiated notification frame.
+    fn notification(method: &str, params: Value) -> Value {
+        serde_json::to_value(JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+        })
+        .unwrap_or_default()
+    }
+
+    /// Handle a single request line, which may be a lone request object or
+    /// a JSON-RPC 2.0 batch array. Returns `None` when nothing should be
+    /// written back, e.g. a batch made up entirely of notifications.
+    async fn handle_request_line(&self, line: &str) -> Option<Value> {
+        let parsed: Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Failed to parse JSON-RPC request: {}", e);
+                return Some(Self::error_response(None, JsonRpcError::parse_error()));
+            }
+        };
+
+        if let Value::Array(items) = parsed {
+            return self.handle_batch(items).await;
+        }
+
+        let request: JsonRpcRequest = match serde_json::from_value(parsed) {
             Ok(req) => req,
             Err(e) => {
                 error!("Failed to parse JSON-RPC request: {}", e);
-                return JsonRpcResponse {
-                    jsonrpc: "2.0".to_string(),
-                    id: None,
-                    result: None,
-                    error: Some(JsonRpcError::parse_error()),
-                };
+                return Some(Self::error_response(None, JsonRpcError::parse_error()));
             }
         };
 
-        // Validate JSON-RPC version
+        // Per the JSON-RPC 2.0 spec, a request with no `id` is a
+        // notification and must never get a response, success or error.
+        let is_notification = request.id.is_none();
+        let response = self.dispatch(request).await;
+
+        if is_notification {
+            None
+        } else {
+            Some(serde_json::to_value(response).unwrap_or_default())
+        }
+    }
+
+    /// Handle a JSON-RPC 2.0 batch: dispatch every element concurrently,
+    /// collect the responses in input order, and drop anything contributed
+    /// by a notification (an element with no `id`). An empty batch is
+    /// itself an invalid request per the spec.
+    async fn handle_batch(&self, items: Vec<Value>) -> Option<Value> {
+        if items.is_empty() {
+            return Some(Self::error_response(None, JsonRpcError::invalid_request()));
+        }
+
+        let responses: Vec<JsonRpcResponse> = futures::future::join_all(items.into_iter().map(
+            |item| async move {
+                match serde_json::from_value::<JsonRpcRequest>(item) {
+                    Ok(request) => {
+                        let is_notification = request.id.is_none();
+                        let response = self.dispatch(request).await;
+                        if is_notification {
+                            None
+                        } else {
+                            Some(response)
+                        }
+                    }
+                    Err(_) => Some(JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: None,
+                        result: None,
+                        error: Some(JsonRpcError::invalid_request()),
+                    }),
+                }
+            },
+        ))
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if responses.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_value(responses).unwrap_or_default())
+        }
+    }
+
+    fn error_response(id: Option<Value>, error: JsonRpcError) -> Value {
+        serde_json::to_value(JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        })
+        .unwrap_or_default()
+    }
+
+    /// Validate the JSON-RPC version and dispatch to `handle_request`.
+    async fn dispatch(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         if request.jsonrpc != "2.0" {
             return JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
@@ -158,14 +440,15 @@ impl JsonRpcServer {
             };
         }
 
-        // Handle the request
         self.handle_request(request).await
     }
 
     /// Handle a parsed JSON-RPC request
-    async fn handle_request(&mut self, request: JsonRpcRequest) -> JsonRpcResponse {
+    async fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         let result = match request.method.as_str() {
             "get_completion" => self.handle_get_completion(&request.params).await,
+            "subscribe_completion" => self.handle_subscribe_completion(&request.params).await,
+            "unsubscribe" => self.handle_unsubscribe(&request.params).await,
             "list_tools" => self.handle_list_tools().await,
             "list_resources" => self.handle_list_resources().await,
             "call_tool" => self.handle_call_tool(&request.params).await,
@@ -187,15 +470,24 @@ impl JsonRpcServer {
             },
             Err(e) => {
                 warn!("Request failed: {}", e);
+                let error = if e.to_string().starts_with("Method not found") {
+                    JsonRpcError::method_not_found(&request.method)
+                } else {
+                    match e.downcast_ref::<HandlerError>() {
+                        Some(HandlerError::InvalidParams(detail)) => {
+                            JsonRpcError::invalid_params(detail.clone())
+                        }
+                        Some(HandlerError::McpFailure { server, detail }) => {
+                            JsonRpcError::mcp_error(server, detail.clone())
+                        }
+                        None => JsonRpcError::internal_error(e.to_string()),
+                    }
+                };
                 JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
                     id: request.id,
                     result: None,
-                    error: Some(if e.to_string().starts_with("Method not found") {
-                        JsonRpcError::method_not_found(&request.method)
-                    } else {
-                        JsonRpcError::internal_error(e.to_string())
-                    }),
+                    error: Some(error),
                 }
             }
         }
@@ -203,7 +495,8 @@ impl JsonRpcServer {
 
     /// Handle get_completion request
     async fn handle_get_completion(&self, params: &Value) -> Result<Value> {
-        let request: CompletionRequest = serde_json::from_value(params.clone())?;
+        let request: CompletionRequest = serde_json::from_value(params.clone())
+            .map_err(|e| HandlerError::InvalidParams(e.to_string()))?;
 
         debug!(
             "Completion request for {}:{} ({})",
@@ -215,6 +508,91 @@ impl JsonRpcServer {
         Ok(serde_json::to_value(response)?)
     }
 
+    /// Handle subscribe_completion request
+    ///
+    /// Spawns a background task that streams completions through
+    /// `CompletionEngine::stream_completions`, pushing a
+    /// `completion/partial` notification as each provider yields and a
+    /// final `completion/complete` notification once every provider has
+    /// been tried (or failed). Returns the subscription id immediately so
+    /// the caller never blocks on slow providers.
+    async fn handle_subscribe_completion(&self, params: &Value) -> Result<Value> {
+        let request: CompletionRequest = serde_json::from_value(params.clone())?;
+
+        let writer_tx = self
+            .writer_tx
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| anyhow!("server is not running"))?;
+
+        let subscription_id = self.next_subscription_id.fetch_add(1, Ordering::SeqCst);
+        let completion_engine = Arc::clone(&self.completion_engine);
+        let subscriptions = Arc::clone(&self.subscriptions);
+
+        let task_tx = writer_tx.clone();
+        let handle = tokio::spawn(async move {
+            let partial_tx = task_tx.clone();
+            let result = completion_engine
+                .stream_completions(&request, move |completions| {
+                    let _ = partial_tx.send(Self::notification(
+                        "completion/partial",
+                        serde_json::json!({
+                            "subscription": subscription_id,
+                            "completions": completions,
+                        }),
+                    ));
+                })
+                .await;
+
+            let complete_params = match result {
+                Ok(response) => serde_json::json!({
+                    "subscription": subscription_id,
+                    "total": response.completions.len(),
+                    "processing_time_ms": response.processing_time_ms,
+                }),
+                Err(e) => {
+                    warn!("Subscription {} failed: {}", subscription_id, e);
+                    serde_json::json!({
+                        "subscription": subscription_id,
+                        "error": e.to_string(),
+                    })
+                }
+            };
+            let _ = task_tx.send(Self::notification("completion/complete", complete_params));
+
+            subscriptions.lock().await.remove(&subscription_id);
+        });
+
+        self.subscriptions
+            .lock()
+            .await
+            .insert(subscription_id, handle);
+
+        Ok(serde_json::json!({ "subscription": subscription_id }))
+    }
+
+    /// Handle unsubscribe request, cancelling an active `subscribe_completion`
+    async fn handle_unsubscribe(&self, params: &Value) -> Result<Value> {
+        #[derive(Deserialize)]
+        struct UnsubscribeParams {
+            subscription: u64,
+        }
+
+        let params: UnsubscribeParams = serde_json::from_value(params.clone())?;
+
+        match self.subscriptions.lock().await.remove(&params.subscription) {
+            Some(handle) => {
+                handle.abort();
+                Ok(serde_json::json!({
+                    "status": "unsubscribed",
+                    "subscription": params.subscription,
+                }))
+            }
+            None => Err(anyhow!("Unknown subscription: {}", params.subscription)),
+        }
+    }
+
     /// Handle list_tools request
     async fn handle_list_tools(&self) -> Result<Value> {
         if let Some(client) = &self.mcp_client {
@@ -245,12 +623,17 @@ impl JsonRpcServer {
             arguments: Option<Value>,
         }
 
-        let params: CallToolParams = serde_json::from_value(params.clone())?;
+        let params: CallToolParams = serde_json::from_value(params.clone())
+            .map_err(|e| HandlerError::InvalidParams(e.to_string()))?;
 
         if let Some(client) = &self.mcp_client {
             let result = client
                 .call_tool(&params.server, &params.tool, params.arguments)
-                .await?;
+                .await
+                .map_err(|e| HandlerError::McpFailure {
+                    server: params.server.clone(),
+                    detail: e.to_string(),
+                })?;
             Ok(serde_json::to_value(result)?)
         } else {
             Err(anyhow!("MCP client not initialized"))
@@ -265,10 +648,17 @@ impl JsonRpcServer {
             uri: String,
         }
 
-        let params: ReadResourceParams = serde_json::from_value(params.clone())?;
+        let params: ReadResourceParams = serde_json::from_value(params.clone())
+            .map_err(|e| HandlerError::InvalidParams(e.to_string()))?;
 
         if let Some(client) = &self.mcp_client {
-            let content = client.read_resource(&params.server, &params.uri).await?;
+            let content = client
+                .read_resource(&params.server, &params.uri)
+                .await
+                .map_err(|e| HandlerError::McpFailure {
+                    server: params.server.clone(),
+                    detail: e.to_string(),
+                })?;
             Ok(serde_json::to_value(content)?)
         } else {
             Err(anyhow!("MCP client not initialized"))
@@ -282,7 +672,7 @@ impl JsonRpcServer {
         });
 
         if let Some(client) = &self.mcp_client {
-            let server_status = client.get_server_status();
+            let server_status = client.get_server_status().await;
             status["mcp_servers"] = serde_json::to_value(server_status)?;
         }
 
@@ -301,6 +691,25 @@ mod tests {
         assert_eq!(JsonRpcError::method_not_found("test").code, -32601);
     }
 
+    #[test]
+    fn test_invalid_params_carries_detail() {
+        let error = JsonRpcError::invalid_params("missing field `file_path`".to_string());
+        assert_eq!(error.code, -32602);
+        assert_eq!(
+            error.data.unwrap()["detail"],
+            "missing field `file_path`"
+        );
+    }
+
+    #[test]
+    fn test_mcp_error_carries_server_and_detail() {
+        let error = JsonRpcError::mcp_error("filesystem", "connection reset".to_string());
+        assert_eq!(error.code, -32001);
+        let data = error.data.unwrap();
+        assert_eq!(data["server"], "filesystem");
+        assert_eq!(data["detail"], "connection reset");
+    }
+
     #[test]
     fn test_jsonrpc_request_parsing() {
         let json = r#"{"jsonrpc":"2.0","id":1,"method":"test","params":{}}"#;
@@ -308,4 +717,27 @@ mod tests {
         assert_eq!(request.method, "test");
         assert_eq!(request.jsonrpc, "2.0");
     }
+
+    #[test]
+    fn test_read_frame_line_delimited() {
+        let mut input = b"{\"a\":1}\n".as_slice();
+        let frame = JsonRpcServer::read_frame(&mut input, FramingMode::LineDelimited).unwrap();
+        assert_eq!(frame, Some(r#"{"a":1}"#.to_string()));
+    }
+
+    #[test]
+    fn test_read_frame_content_length() {
+        let body = r#"{"a":1}"#;
+        let input = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        let mut input = input.as_bytes();
+        let frame = JsonRpcServer::read_frame(&mut input, FramingMode::ContentLength).unwrap();
+        assert_eq!(frame, Some(body.to_string()));
+    }
+
+    #[test]
+    fn test_write_frame_content_length() {
+        let mut out = Vec::new();
+        JsonRpcServer::write_frame(&mut out, FramingMode::ContentLength, r#"{"a":1}"#).unwrap();
+        assert_eq!(out, b"Content-Length: 7\r\n\r\n{\"a\":1}");
+    }
 }
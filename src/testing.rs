@@ -0,0 +1,228 @@
+//! Test doubles for [`crate::client::MCPService`], enabled by the `testing`
+//! feature so downstream consumers can exercise [`crate::client::MCPClient`]
+//! without spawning real child processes or network connections.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use crate::client::{MCPService, Resource, ResourceContent, Tool, ToolResult};
+
+/// An `MCPService` with canned responses configured up front and replayed
+/// verbatim by each trait method, with an optional injected failure per
+/// method to exercise `ConnectionStatus::Failed` and reconnection paths.
+pub struct MockMCPService {
+    tools: Vec<Tool>,
+    tools_err: Option<String>,
+    call_tool_response: ToolResult,
+    call_tool_err: Option<String>,
+    resources: Vec<Resource>,
+    resources_err: Option<String>,
+    resource_content: ResourceContent,
+    read_resource_err: Option<String>,
+    disconnect_err: Option<String>,
+}
+
+impl MockMCPService {
+    pub fn new() -> Self {
+        Self {
+            tools: Vec::new(),
+            tools_err: None,
+            call_tool_response: ToolResult {
+                content: Vec::new(),
+                is_error: false,
+            },
+            call_tool_err: None,
+            resources: Vec::new(),
+            resources_err: None,
+            resource_content: ResourceContent {
+                uri: String::new(),
+                mime_type: None,
+                text: None,
+                blob: None,
+            },
+            read_resource_err: None,
+            disconnect_err: None,
+        }
+    }
+
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    pub fn with_tools_error(mut self, message: impl Into<String>) -> Self {
+        self.tools_err = Some(message.into());
+        self
+    }
+
+    pub fn with_call_tool_response(mut self, response: ToolResult) -> Self {
+        self.call_tool_response = response;
+        self
+    }
+
+    pub fn with_call_tool_error(mut self, message: impl Into<String>) -> Self {
+        self.call_tool_err = Some(message.into());
+        self
+    }
+
+    pub fn with_resources(mut self, resources: Vec<Resource>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    pub fn with_resources_error(mut self, message: impl Into<String>) -> Self {
+        self.resources_err = Some(message.into());
+        self
+    }
+
+    pub fn with_resource_content(mut self, content: ResourceContent) -> Self {
+        self.resource_content = content;
+        self
+    }
+
+    pub fn with_read_resource_error(mut self, message: impl Into<String>) -> Self {
+        self.read_resource_err = Some(message.into());
+        self
+    }
+
+    pub fn with_disconnect_error(mut self, message: impl Into<String>) -> Self {
+        self.disconnect_err = Some(message.into());
+        self
+    }
+}
+
+impl Default for MockMCPService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl MCPService for MockMCPService {
+    async fn list_tools(&self) -> Result<Vec<Tool>> {
+        match &self.tools_err {
+            Some(message) => Err(anyhow!(message.clone())),
+            None => Ok(self.tools.clone()),
+        }
+    }
+
+    async fn call_tool(&self, _name: &str, _arguments: Option<Value>) -> Result<ToolResult> {
+        match &self.call_tool_err {
+            Some(message) => Err(anyhow!(message.clone())),
+            None => Ok(self.call_tool_response.clone()),
+        }
+    }
+
+    async fn list_resources(&self) -> Result<Vec<Resource>> {
+        match &self.resources_err {
+            Some(message) => Err(anyhow!(message.clone())),
+            None => Ok(self.resources.clone()),
+        }
+    }
+
+    async fn read_resource(&self, _uri: &str) -> Result<ResourceContent> {
+        match &self.read_resource_err {
+            Some(message) => Err(anyhow!(message.clone())),
+            None => Ok(self.resource_content.clone()),
+        }
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        match &self.disconnect_err {
+            Some(message) => Err(anyhow!(message.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{ConnectionStatus, MCPClient};
+    use crate::config::MCPConfig;
+    use std::collections::HashMap;
+
+    fn empty_config() -> MCPConfig {
+        MCPConfig {
+            mcp_servers: indexmap::IndexMap::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    fn mock_local_config() -> crate::config::ServerConfig {
+        crate::config::ServerConfig::Local {
+            command: "mock".to_string(),
+            args: None,
+            env: None,
+            disabled: None,
+            install: None,
+            version: None,
+        }
+    }
+
+    fn client_with(services: HashMap<String, Box<dyn MCPService>>) -> MCPClient {
+        let mut config = empty_config();
+        for name in services.keys() {
+            config.mcp_servers.insert(name.clone(), mock_local_config());
+        }
+        MCPClient::with_services(config, services)
+    }
+
+    #[tokio::test]
+    async fn call_tool_on_unknown_server_errors() {
+        let client = client_with(HashMap::new());
+        let err = client.call_tool("missing", "tool", None).await.unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn call_tool_on_disconnected_server_errors() {
+        let mut config = empty_config();
+        config
+            .mcp_servers
+            .insert("down".to_string(), mock_local_config());
+        let client = MCPClient::with_services(config, HashMap::new());
+
+        let err = client.call_tool("down", "tool", None).await.unwrap_err();
+        assert!(err.to_string().contains("not connected"));
+    }
+
+    #[tokio::test]
+    async fn call_tool_surfaces_service_error() {
+        let mut services: HashMap<String, Box<dyn MCPService>> = HashMap::new();
+        services.insert(
+            "flaky".to_string(),
+            Box::new(MockMCPService::new().with_call_tool_error("boom")),
+        );
+        let client = client_with(services);
+
+        let err = client.call_tool("flaky", "tool", None).await.unwrap_err();
+        assert!(err.to_string().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn read_resource_surfaces_service_error() {
+        let mut services: HashMap<String, Box<dyn MCPService>> = HashMap::new();
+        services.insert(
+            "flaky".to_string(),
+            Box::new(MockMCPService::new().with_read_resource_error("nope")),
+        );
+        let client = client_with(services);
+
+        let err = client
+            .read_resource("flaky", "file:///tmp/x")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("nope"));
+    }
+
+    #[tokio::test]
+    async fn with_services_marks_supplied_servers_connected() {
+        let mut services: HashMap<String, Box<dyn MCPService>> = HashMap::new();
+        services.insert("up".to_string(), Box::new(MockMCPService::new()));
+        let client = client_with(services);
+
+        let status = client.get_server_status().await;
+        assert!(matches!(status.get("up"), Some(ConnectionStatus::Connected)));
+    }
+}